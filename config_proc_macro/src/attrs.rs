@@ -1,8 +1,8 @@
 //! This module provides utilities for handling attributes on variants
-//! of `config_type` enum. Currently there are two types of attributes
-//! that could appear on the variants of `config_type` enum: `doc_hint`
-//! and `value`. Both comes in the form of name-value pair whose value
-//! is string literal.
+//! of `config_type` enum. Currently there are three types of attributes
+//! that could appear on the variants of `config_type` enum: `doc_hint`,
+//! `value` and `hidden`. The first two come in the form of name-value pair
+//! whose value is string literal, while `hidden` is a bare word.
 
 /// Returns the value of the first `doc_hint` attribute in the given slice or
 /// `None` if `doc_hint` attribute is not available.
@@ -38,6 +38,27 @@ pub fn is_config_value(attr: &syn::Attribute) -> bool {
     is_attr_name_value(attr, "value")
 }
 
+/// Returns the doc comment of the given attributes, with the lines joined by newlines, or an
+/// empty string if there is no doc comment.
+pub fn find_doc_comment(attrs: &[syn::Attribute]) -> String {
+    attrs
+        .iter()
+        .filter_map(|attr| get_name_value_str_lit(attr, "doc"))
+        .map(|line| line.trim().to_owned())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Returns `true` if the given attribute is a `hidden` attribute.
+pub fn is_hidden(attr: &syn::Attribute) -> bool {
+    matches!(attr.parse_meta(), Ok(syn::Meta::Path(ref path)) if path.is_ident("hidden"))
+}
+
+/// Returns `true` if any of the given attributes is a `hidden` attribute.
+pub fn has_hidden(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(is_hidden)
+}
+
 fn is_attr_name_value(attr: &syn::Attribute, name: &str) -> bool {
     attr.parse_meta().ok().map_or(false, |meta| matches!(meta, syn::Meta::NameValue(syn::MetaNameValue { ref path, .. }) if path.is_ident(name)))
 }
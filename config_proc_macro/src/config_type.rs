@@ -3,13 +3,46 @@ use proc_macro2::TokenStream;
 use crate::item_enum::define_config_type_on_enum;
 use crate::item_struct::define_config_type_on_struct;
 
+/// Options passed to `config_type`, e.g., `#[config_type(repr_u8)]`.
+#[derive(Default)]
+pub struct ConfigTypeArgs {
+    /// Generates `as_u8` and `from_u8` using the declaration order of the variants.
+    pub repr_u8: bool,
+    /// Warns about `doc_hint`s that make the generated help text hard to read.
+    pub lint_hints: bool,
+}
+
+impl ConfigTypeArgs {
+    fn parse(args: &syn::AttributeArgs) -> syn::Result<Self> {
+        let mut config_type_args = ConfigTypeArgs::default();
+        for arg in args {
+            match arg {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("repr_u8") => {
+                    config_type_args.repr_u8 = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("lint_hints") => {
+                    config_type_args.lint_hints = true;
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        arg,
+                        "unknown `config_type` argument, expected `repr_u8` or `lint_hints`",
+                    ));
+                }
+            }
+        }
+        Ok(config_type_args)
+    }
+}
+
 /// Defines `config_type` on enum or struct.
 // FIXME: Implement this on struct.
-pub fn define_config_type(input: &syn::Item) -> TokenStream {
-    match input {
-        syn::Item::Struct(st) => define_config_type_on_struct(st),
-        syn::Item::Enum(en) => define_config_type_on_enum(en),
-        _ => panic!("Expected enum or struct"),
-    }
-    .unwrap()
+pub fn define_config_type(args: &syn::AttributeArgs, input: &syn::Item) -> TokenStream {
+    ConfigTypeArgs::parse(args)
+        .and_then(|args| match input {
+            syn::Item::Struct(st) => define_config_type_on_struct(st),
+            syn::Item::Enum(en) => define_config_type_on_enum(&args, en),
+            _ => panic!("Expected enum or struct"),
+        })
+        .unwrap_or_else(|err| err.to_compile_error())
 }
@@ -2,12 +2,16 @@ use proc_macro2::TokenStream;
 use quote::quote;
 
 use crate::attrs::*;
+use crate::config_type::ConfigTypeArgs;
 use crate::utils::*;
 
 type Variants = syn::punctuated::Punctuated<syn::Variant, syn::Token![,]>;
 
 /// Defines and implements `config_type` enum.
-pub fn define_config_type_on_enum(em: &syn::ItemEnum) -> syn::Result<TokenStream> {
+pub fn define_config_type_on_enum(
+    args: &ConfigTypeArgs,
+    em: &syn::ItemEnum,
+) -> syn::Result<TokenStream> {
     let syn::ItemEnum {
         vis,
         enum_token,
@@ -17,6 +21,27 @@ pub fn define_config_type_on_enum(em: &syn::ItemEnum) -> syn::Result<TokenStream
         ..
     } = em;
 
+    if variants.is_empty() {
+        return Err(syn::Error::new_spanned(
+            em,
+            "a `config_type` enum must have at least one variant",
+        ));
+    }
+    for attr in variants.iter().flat_map(|v| v.attrs.iter()) {
+        match config_value(attr) {
+            Some(value) if value.trim() != value => {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    format!(
+                        "`value` must not have leading or trailing whitespace, use `{:?}`",
+                        value.trim()
+                    ),
+                ));
+            }
+            _ => {}
+        }
+    }
+
     let mod_name_str = format!("__define_config_type_on_enum_{}", ident);
     let mod_name = syn::Ident::new(&mod_name_str, ident.span());
     let variants = fold_quote(variants.iter().map(process_variant), |meta| quote!(#meta,));
@@ -25,7 +50,34 @@ pub fn define_config_type_on_enum(em: &syn::ItemEnum) -> syn::Result<TokenStream
     let impl_from_str = impl_from_str(&em.ident, &em.variants);
     let impl_display = impl_display(&em.ident, &em.variants);
     let impl_serde = impl_serde(&em.ident, &em.variants);
-    let impl_deserialize = impl_deserialize(&em.ident, &em.variants);
+    let impl_unit_variants = impl_unit_variants(&em.ident, &em.variants);
+    let impl_variant_count = impl_variant_count(&em.ident, &em.variants);
+    let impl_resolve = impl_resolve(&em.ident);
+    let impl_variant_doc = impl_variant_doc(&em.ident, &em.variants);
+    let impl_variants_doc_table = impl_variants_doc_table(&em.ident, &em.variants);
+    let impl_anchor = impl_anchor(&em.ident, &em.variants);
+    let impl_into_str = impl_into_str(&em.ident, &em.variants);
+    let impl_from_u64 = impl_from_u64(&em.ident, &em.variants);
+    let impl_repr_u8 = if args.repr_u8 {
+        impl_repr_u8(em)?
+    } else {
+        TokenStream::new()
+    };
+    let lint_hints = if args.lint_hints {
+        lint_hints(&em.variants)
+    } else {
+        TokenStream::new()
+    };
+    let impl_deserialize = if cfg!(feature = "serde") {
+        impl_deserialize(&em.ident, &em.variants)
+    } else {
+        TokenStream::new()
+    };
+    let impl_arbitrary = if cfg!(feature = "arbitrary") {
+        impl_arbitrary(&em.ident, &em.variants)
+    } else {
+        TokenStream::new()
+    };
 
     Ok(quote! {
         #[allow(non_snake_case)]
@@ -37,6 +89,17 @@ pub fn define_config_type_on_enum(em: &syn::ItemEnum) -> syn::Result<TokenStream
             #impl_from_str
             #impl_serde
             #impl_deserialize
+            #impl_arbitrary
+            #impl_unit_variants
+            #impl_variant_count
+            #impl_resolve
+            #impl_variant_doc
+            #impl_variants_doc_table
+            #impl_anchor
+            #impl_into_str
+            #impl_from_u64
+            #impl_repr_u8
+            #lint_hints
         }
         #vis use #mod_name::#ident;
     })
@@ -47,15 +110,25 @@ fn process_variant(variant: &syn::Variant) -> TokenStream {
     let metas = variant
         .attrs
         .iter()
-        .filter(|attr| !is_doc_hint(attr) && !is_config_value(attr));
+        .filter(|attr| !is_doc_hint(attr) && !is_config_value(attr) && !is_hidden(attr));
     let attrs = fold_quote(metas, |meta| quote!(#meta));
-    let syn::Variant { ident, fields, .. } = variant;
-    quote!(#attrs #ident #fields)
+    let syn::Variant {
+        ident,
+        fields,
+        discriminant,
+        ..
+    } = variant;
+    let discriminant = discriminant
+        .as_ref()
+        .map(|(eq_token, expr)| quote!(#eq_token #expr));
+    quote!(#attrs #ident #fields #discriminant)
 }
 
 fn impl_doc_hint(ident: &syn::Ident, variants: &Variants) -> TokenStream {
+    // Hidden variants can still be parsed, but are not advertised to users.
     let doc_hint = variants
         .iter()
+        .filter(|v| !has_hidden(&v.attrs))
         .map(doc_hint_of_variant)
         .collect::<Vec<_>>()
         .join("|");
@@ -64,12 +137,49 @@ fn impl_doc_hint(ident: &syn::Ident, variants: &Variants) -> TokenStream {
         use crate::config::ConfigType;
         impl ConfigType for #ident {
             fn doc_hint() -> String {
-                #doc_hint.to_owned()
+                Self::DOC_HINT.to_owned()
+            }
+        }
+
+        impl #ident {
+            /// The value of `doc_hint`, usable without allocating, e.g., `[Foo|Bar]`.
+            pub const DOC_HINT: &'static str = #doc_hint;
+
+            /// Returns a one-line summary of the accepted values and the given default, e.g.,
+            /// `[Foo|Bar] (default: Foo)`.
+            pub fn cli_help_line(default: Self) -> String {
+                format!("{} (default: {})", Self::DOC_HINT, default)
             }
         }
     }
 }
 
+const MAX_DOC_HINT_LEN: usize = 40;
+
+// Proc macros cannot emit warnings on stable, so this refers to a deprecated constant for each
+// overlong `doc_hint`, which makes the compiler warn with our note instead.
+fn lint_hints(variants: &Variants) -> TokenStream {
+    let long_hints = variants
+        .iter()
+        .filter(|v| !has_hidden(&v.attrs))
+        .map(|v| (&v.ident, doc_hint_of_variant(v)))
+        .filter(|(_, doc_hint)| doc_hint.chars().count() > MAX_DOC_HINT_LEN);
+    fold_quote(long_hints, |(v, doc_hint)| {
+        let const_name = syn::Ident::new(&format!("__long_doc_hint_{}", v), v.span());
+        let note = format!(
+            "`doc_hint` of `{}` is longer than {} characters, which makes `--help` hard to read: \
+             {:?}",
+            v, MAX_DOC_HINT_LEN, doc_hint
+        );
+        quote! {
+            #[deprecated(note = #note)]
+            #[allow(non_upper_case_globals)]
+            const #const_name: () = ();
+            const _: () = #const_name;
+        }
+    })
+}
+
 fn impl_display(ident: &syn::Ident, variants: &Variants) -> TokenStream {
     let vs = variants
         .iter()
@@ -105,18 +215,325 @@ fn impl_from_str(ident: &syn::Ident, variants: &Variants) -> TokenStream {
             }
         }
     });
+    // Hidden variants still parse, but are not advertised to users.
     let mut err_msg = String::from("Bad variant, expected one of:");
-    for v in variants.iter().filter(|v| is_unit(v)) {
+    for v in variants
+        .iter()
+        .filter(|v| is_unit(v) && !has_hidden(&v.attrs))
+    {
         err_msg.push_str(&format!(" `{}`", v.ident));
     }
+    // The error stays a `&'static str`, so every message with a suggestion is built up front.
+    let suggestions = variants
+        .iter()
+        .filter(|v| is_unit(v) && !has_hidden(&v.attrs))
+        .map(|v| {
+            let value = config_value_of_variant(v);
+            let msg = format!("{}; did you mean `{}`?", err_msg, value);
+            quote!((#value, #msg))
+        });
 
     quote! {
         impl ::std::str::FromStr for #ident {
             type Err = &'static str;
 
             fn from_str(s: &str) -> Result<Self, Self::Err> {
+                fn edit_distance(a: &str, b: &str) -> usize {
+                    let b: Vec<char> = b.chars().collect();
+                    let mut row: Vec<usize> = (0..=b.len()).collect();
+                    for (i, ca) in a.chars().enumerate() {
+                        let mut diagonal = row[0];
+                        row[0] = i + 1;
+                        for (j, &cb) in b.iter().enumerate() {
+                            let above = row[j + 1];
+                            row[j + 1] = if ca == cb {
+                                diagonal
+                            } else {
+                                1 + diagonal.min(row[j]).min(above)
+                            };
+                            diagonal = above;
+                        }
+                    }
+                    row[b.len()]
+                }
+
                 #if_patterns
-                return Err(#err_msg);
+                let suggestions: &[(&str, &'static str)] = &[#(#suggestions),*];
+                let input = s.to_ascii_lowercase();
+                let closest = suggestions
+                    .iter()
+                    .map(|&(value, msg)| (edit_distance(&input, &value.to_ascii_lowercase()), msg))
+                    .min_by_key(|&(distance, _)| distance);
+                match closest {
+                    Some((distance, msg)) if distance <= ::std::cmp::max(input.len(), 3) / 3 => {
+                        Err(msg)
+                    }
+                    _ => Err(#err_msg),
+                }
+            }
+        }
+
+        impl<'a> ::std::convert::TryFrom<&'a str> for #ident {
+            type Error = <#ident as ::std::str::FromStr>::Err;
+
+            fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+                <#ident as ::std::str::FromStr>::from_str(s)
+            }
+        }
+    }
+}
+
+// Only generated for enums whose variants are all unit variants.
+fn impl_unit_variants(ident: &syn::Ident, variants: &Variants) -> TokenStream {
+    if !variants.iter().all(is_unit) {
+        return TokenStream::new();
+    }
+
+    let vs = variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+    let next_vs = vs.iter().cycle().skip(1);
+    let prev_vs = vs.iter().cycle().skip(vs.len().saturating_sub(1));
+    let next_arms = fold_quote(vs.iter().zip(next_vs), |(v, next)| {
+        quote! {
+            #ident::#v => #ident::#next,
+        }
+    });
+    let prev_arms = fold_quote(vs.iter().zip(prev_vs), |(v, prev)| {
+        quote! {
+            #ident::#v => #ident::#prev,
+        }
+    });
+
+    quote! {
+        impl #ident {
+            /// Returns every variant in declaration order. Tests can compare its length against
+            /// the number of variants they handle, so that adding a variant fails them. Unlike
+            /// `doc_hint`, this includes hidden variants, which can still be parsed.
+            pub fn all_variants() -> &'static [Self] {
+                &[#(#ident::#vs),*]
+            }
+
+            /// Returns the variant declared after `self`, wrapping around to the first one.
+            pub fn next(self) -> Self {
+                match self {
+                    #next_arms
+                }
+            }
+
+            /// Returns the variant declared before `self`, wrapping around to the last one.
+            pub fn prev(self) -> Self {
+                match self {
+                    #prev_arms
+                }
+            }
+        }
+    }
+}
+
+fn impl_variant_count(ident: &syn::Ident, variants: &Variants) -> TokenStream {
+    let count = variants.len();
+    let values = variants
+        .iter()
+        .filter(|v| is_unit(v))
+        .map(config_value_of_variant);
+    quote! {
+        impl #ident {
+            /// The number of variants, including data-carrying and hidden ones.
+            pub const VARIANT_COUNT: usize = #count;
+
+            /// The value strings of the unit variants in declaration order, usable in const
+            /// contexts unlike `all_variants`. Like `all_variants`, this includes hidden variants.
+            pub const VALUES: &'static [&'static str] = &[#(#values),*];
+        }
+    }
+}
+
+fn impl_resolve(ident: &syn::Ident) -> TokenStream {
+    quote! {
+        impl #ident {
+            /// Returns the value of the first layer that sets one, e.g., of the partial configs
+            /// from the most to the least specific, or `default` if no layer does.
+            pub fn resolve(layers: &[Option<Self>], default: Self) -> Self {
+                layers.iter().find_map(|layer| *layer).unwrap_or(default)
+            }
+        }
+    }
+}
+
+// Only generated for enums whose variants are all unit variants with numeric values.
+fn impl_from_u64(ident: &syn::Ident, variants: &Variants) -> TokenStream {
+    let values = variants
+        .iter()
+        .map(|v| match v.fields {
+            syn::Fields::Unit => config_value_of_variant(v).parse::<u64>().ok(),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>();
+    let values = match values {
+        Some(values) => values,
+        None => return TokenStream::new(),
+    };
+
+    let arms = fold_quote(values.iter().zip(variants.iter()), |(n, v)| {
+        let v_ident = &v.ident;
+        quote! {
+            #n => Some(#ident::#v_ident),
+        }
+    });
+
+    quote! {
+        impl #ident {
+            /// Returns the variant whose value is `n`, or `None` if there is no such variant.
+            pub fn from_u64(n: u64) -> Option<Self> {
+                match n {
+                    #arms
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+// Numbers the variants in declaration order, regardless of their `value`s.
+fn impl_repr_u8(em: &syn::ItemEnum) -> syn::Result<TokenStream> {
+    let ident = &em.ident;
+    if let Some(v) = em.variants.iter().find(|v| !is_unit(v)) {
+        return Err(syn::Error::new_spanned(
+            v,
+            "`repr_u8` requires every variant to be a unit variant",
+        ));
+    }
+    if em.variants.len() > usize::from(u8::MAX) + 1 {
+        return Err(syn::Error::new_spanned(
+            em,
+            "`repr_u8` supports at most 256 variants",
+        ));
+    }
+
+    let vs = em.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+    let ns = (0..vs.len()).map(|n| n as u8).collect::<Vec<_>>();
+
+    Ok(quote! {
+        impl #ident {
+            /// Returns the position of this variant in the declaration order.
+            pub fn as_u8(&self) -> u8 {
+                match self {
+                    #(#ident::#vs => #ns,)*
+                }
+            }
+
+            /// Returns the variant at position `n` in the declaration order, or `None` if there
+            /// is no such variant.
+            pub fn from_u8(n: u8) -> Option<Self> {
+                match n {
+                    #(#ns => Some(#ident::#vs),)*
+                    _ => None,
+                }
+            }
+        }
+    })
+}
+
+fn impl_variant_doc(ident: &syn::Ident, variants: &Variants) -> TokenStream {
+    let arms = fold_quote(variants.iter(), |v| {
+        let v_ident = &v.ident;
+        let pattern = match v.fields {
+            syn::Fields::Named(..) => quote!(#ident::#v_ident{..}),
+            syn::Fields::Unnamed(..) => quote!(#ident::#v_ident(..)),
+            syn::Fields::Unit => quote!(#ident::#v_ident),
+        };
+        let doc = find_doc_comment(&v.attrs);
+        quote! {
+            #pattern => #doc,
+        }
+    });
+
+    quote! {
+        impl #ident {
+            /// Returns the doc comment of this variant, or an empty string if it has none.
+            pub fn variant_doc(&self) -> &'static str {
+                match self {
+                    #arms
+                }
+            }
+        }
+    }
+}
+
+// Data-carrying variants are left out, since they have no single value string.
+fn impl_variants_doc_table(ident: &syn::Ident, variants: &Variants) -> TokenStream {
+    let rows = fold_quote(variants.iter().filter(|v| is_unit(v)), |v| {
+        let value = config_value_of_variant(v);
+        let doc = find_doc_hint(&v.attrs).unwrap_or_else(|| find_doc_comment(&v.attrs));
+        quote! {
+            (#value, #doc),
+        }
+    });
+
+    quote! {
+        impl #ident {
+            /// Returns the value string of every unit variant paired with its `doc_hint`, or with
+            /// its doc comment if it has no `doc_hint`.
+            pub fn variants_doc_table() -> Vec<(&'static str, &'static str)> {
+                vec![#rows]
+            }
+        }
+    }
+}
+
+// Only generated for enums whose variants are all unit variants.
+fn impl_into_str(ident: &syn::Ident, variants: &Variants) -> TokenStream {
+    if !variants.iter().all(is_unit) {
+        return TokenStream::new();
+    }
+
+    let arms = fold_quote(variants.iter(), |v| {
+        let v_ident = &v.ident;
+        let value = config_value_of_variant(v);
+        quote! {
+            #ident::#v_ident => #value,
+        }
+    });
+
+    quote! {
+        impl<'a> From<&'a #ident> for &'static str {
+            fn from(value: &'a #ident) -> Self {
+                match value {
+                    #arms
+                }
+            }
+        }
+
+        impl From<#ident> for &'static str {
+            fn from(value: #ident) -> Self {
+                From::from(&value)
+            }
+        }
+    }
+}
+
+// Data-carrying variants have no value string, so their anchor is derived from the variant name.
+fn impl_anchor(ident: &syn::Ident, variants: &Variants) -> TokenStream {
+    let arms = fold_quote(variants.iter(), |v| {
+        let v_ident = &v.ident;
+        let pattern = match v.fields {
+            syn::Fields::Named(..) => quote!(#ident::#v_ident{..}),
+            syn::Fields::Unnamed(..) => quote!(#ident::#v_ident(..)),
+            syn::Fields::Unit => quote!(#ident::#v_ident),
+        };
+        let anchor = slugify(&config_value_of_variant(v));
+        quote! {
+            #pattern => #anchor,
+        }
+    });
+
+    quote! {
+        impl #ident {
+            /// Returns the value string of this variant slugified for use as an HTML anchor,
+            /// e.g., `always-next-line` for `AlwaysNextLine`.
+            pub fn anchor(&self) -> &'static str {
+                match self {
+                    #arms
+                }
             }
         }
     }
@@ -126,8 +543,21 @@ fn doc_hint_of_variant(variant: &syn::Variant) -> String {
     find_doc_hint(&variant.attrs).unwrap_or_else(|| variant.ident.to_string())
 }
 
+/// Returns the value of `#[value]`, or the discriminant if it is an integer literal, e.g., `"3"`
+/// for `Foo = 3`, or the name of the variant.
 fn config_value_of_variant(variant: &syn::Variant) -> String {
-    find_config_value(&variant.attrs).unwrap_or_else(|| variant.ident.to_string())
+    find_config_value(&variant.attrs)
+        .or_else(|| match &variant.discriminant {
+            Some((
+                _,
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(lit),
+                    ..
+                }),
+            )) => Some(lit.base10_digits().to_owned()),
+            _ => None,
+        })
+        .unwrap_or_else(|| variant.ident.to_string())
 }
 
 fn impl_serde(ident: &syn::Ident, variants: &Variants) -> TokenStream {
@@ -160,19 +590,25 @@ fn impl_serde(ident: &syn::Ident, variants: &Variants) -> TokenStream {
     }
 }
 
-// Currently only unit variants are supported.
-fn impl_deserialize(ident: &syn::Ident, variants: &Variants) -> TokenStream {
-    let supported_vs = variants.iter().filter(|v| is_unit(v));
-    let if_patterns = fold_quote(supported_vs, |v| {
-        let config_value = config_value_of_variant(v);
-        let variant_ident = &v.ident;
-        quote! {
-            if #config_value.eq_ignore_ascii_case(s) {
-                return Ok(#ident::#variant_ident);
+// Only generated for enums whose variants are all unit variants.
+fn impl_arbitrary(ident: &syn::Ident, variants: &Variants) -> TokenStream {
+    if !variants.iter().all(is_unit) {
+        return TokenStream::new();
+    }
+
+    let vs = variants.iter().map(|v| &v.ident);
+    quote! {
+        impl<'a> ::arbitrary::Arbitrary<'a> for #ident {
+            fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Self> {
+                u.choose(&[#(#ident::#vs),*]).map(|v| *v)
             }
         }
-    });
+    }
+}
 
+// Currently only unit variants are supported. Deserialization goes through the generated
+// `FromStr` so that both the config file and the command line accept the same values.
+fn impl_deserialize(ident: &syn::Ident, variants: &Variants) -> TokenStream {
     let supported_vs = variants.iter().filter(|v| is_unit(v));
     let allowed = fold_quote(supported_vs.map(config_value_of_variant), |s| quote!(#s,));
 
@@ -198,10 +634,9 @@ fn impl_deserialize(ident: &syn::Ident, variants: &Variants) -> TokenStream {
                 }
                 let s = &d.deserialize_string(StringOnly::<D>(PhantomData))?;
 
-                #if_patterns
-
                 static ALLOWED: &'static[&str] = &[#allowed];
-                Err(D::Error::unknown_variant(&s, ALLOWED))
+                <#ident as ::std::str::FromStr>::from_str(s)
+                    .map_err(|_| D::Error::unknown_variant(&s, ALLOWED))
             }
         }
     }
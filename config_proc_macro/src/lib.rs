@@ -14,9 +14,10 @@ use proc_macro::TokenStream;
 use syn::parse_macro_input;
 
 #[proc_macro_attribute]
-pub fn config_type(_args: TokenStream, input: TokenStream) -> TokenStream {
+pub fn config_type(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as syn::AttributeArgs);
     let input = parse_macro_input!(input as syn::Item);
-    let output = config_type::define_config_type(&input);
+    let output = config_type::define_config_type(&args, &input);
 
     if std::env::var("RUSTFMT_DEV_DEBUG_PROC_MACRO").is_ok() {
         utils::debug_with_rustfmt(&output);
@@ -18,6 +18,31 @@ pub fn is_unit(v: &syn::Variant) -> bool {
     matches!(v.fields, syn::Fields::Unit)
 }
 
+/// Turns a config value string into an HTML anchor, e.g., `AlwaysNextLine` into
+/// `always-next-line`: words are split at case changes, lowercased and joined by `-`.
+pub fn slugify(value: &str) -> String {
+    let mut slug = String::with_capacity(value.len());
+    let mut prev_is_lower_or_digit = false;
+    for c in value.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+            prev_is_lower_or_digit = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && prev_is_lower_or_digit {
+            slug.push('-');
+        }
+        prev_is_lower_or_digit = c.is_ascii_lowercase() || c.is_ascii_digit();
+        slug.push(c.to_ascii_lowercase());
+    }
+    if slug.ends_with('-') {
+        slug.pop();
+    }
+    slug
+}
+
 /// Pretty-print the output of proc macro using rustfmt.
 pub(crate) fn debug_with_rustfmt(input: &TokenStream) {
     use std::io::Write;
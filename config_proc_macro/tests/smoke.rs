@@ -17,4 +17,318 @@ mod tests {
         FooBar,
         FooFoo(i32),
     }
+
+    #[config_type]
+    enum Baz {
+        Foo,
+        Bar,
+        Baz,
+    }
+
+    #[config_type]
+    enum Qux {
+        Files,
+        Stdout,
+        #[hidden]
+        ModifiedLines,
+    }
+
+    #[config_type]
+    enum Switch {
+        /// Turn it on.
+        ///
+        /// e.g., `switch = "On"`
+        On,
+        #[value = "off"]
+        Off,
+    }
+
+    #[test]
+    fn cli_help_line() {
+        assert_eq!(
+            Switch::cli_help_line(Switch::Off),
+            "[On|Off] (default: off)"
+        );
+    }
+
+    #[test]
+    fn variant_doc() {
+        assert_eq!(
+            Switch::On.variant_doc(),
+            "Turn it on.\n\ne.g., `switch = \"On\"`"
+        );
+        assert_eq!(Switch::Off.variant_doc(), "");
+    }
+
+    #[config_type]
+    enum Color {
+        /// Always use colors.
+        #[value = "always"]
+        Always,
+        /// Never use colors.
+        #[doc_hint = "no colors"]
+        Never,
+        Custom(u8),
+    }
+
+    #[config_type]
+    enum Brace {
+        AlwaysNextLine,
+        #[value = "same_line"]
+        SameLine,
+        Custom(u8),
+    }
+
+    #[test]
+    fn anchor() {
+        assert_eq!(Brace::AlwaysNextLine.anchor(), "always-next-line");
+        assert_eq!(Brace::SameLine.anchor(), "same-line");
+        assert_eq!(Brace::Custom(0).anchor(), "custom");
+        assert_eq!(Color::Always.anchor(), "always");
+    }
+
+    #[test]
+    fn variants_doc_table() {
+        assert_eq!(
+            Color::variants_doc_table(),
+            vec![("always", "Always use colors."), ("Never", "no colors")]
+        );
+    }
+
+    #[test]
+    fn hidden_variant() {
+        use crate::config::ConfigType;
+
+        assert_eq!(Qux::doc_hint(), "[Files|Stdout]");
+        assert_eq!("ModifiedLines".parse::<Qux>(), Ok(Qux::ModifiedLines));
+    }
+
+    #[config_type]
+    enum Version {
+        #[value = "1"]
+        One,
+        #[value = "2"]
+        Two,
+    }
+
+    #[test]
+    fn from_u64() {
+        assert_eq!(Version::from_u64(2), Some(Version::Two));
+        assert_eq!(Version::from_u64(99), None);
+    }
+
+    #[test]
+    fn try_from_str() {
+        use std::convert::TryFrom;
+
+        assert_eq!(Baz::try_from("bar"), Ok(Baz::Bar));
+        assert_eq!(
+            Baz::try_from("qux"),
+            Err("Bad variant, expected one of: `Foo` `Bar` `Baz`")
+        );
+    }
+
+    #[config_type]
+    enum Newline {
+        Auto,
+        Native,
+        Unix,
+        Windows,
+    }
+
+    #[test]
+    fn from_str_suggests_closest_value() {
+        assert_eq!(
+            "Unixx".parse::<Newline>(),
+            Err(
+                "Bad variant, expected one of: `Auto` `Native` `Unix` `Windows`; \
+                 did you mean `Unix`?"
+            )
+        );
+        assert_eq!(
+            "windoes".parse::<Newline>(),
+            Err(
+                "Bad variant, expected one of: `Auto` `Native` `Unix` `Windows`; \
+                 did you mean `Windows`?"
+            )
+        );
+        assert_eq!(
+            "carriage".parse::<Newline>(),
+            Err("Bad variant, expected one of: `Auto` `Native` `Unix` `Windows`")
+        );
+        assert_eq!(
+            "Modifiedlines".parse::<Qux>(),
+            Ok(Qux::ModifiedLines),
+            "hidden values still parse"
+        );
+        assert_eq!(
+            "ModifiedLine".parse::<Qux>(),
+            Err("Bad variant, expected one of: `Files` `Stdout`")
+        );
+    }
+
+    #[config_type(repr_u8)]
+    enum Level {
+        #[value = "high"]
+        High,
+        Medium,
+        #[value = "0"]
+        Low,
+    }
+
+    #[config_type]
+    enum Discriminant {
+        Foo = 3,
+        #[value = "four"]
+        Bar = 4,
+        Baz = 2 + 3,
+    }
+
+    #[test]
+    fn integer_discriminant_is_value() {
+        assert_eq!(Discriminant::Foo.to_string(), "3");
+        assert_eq!("3".parse::<Discriminant>(), Ok(Discriminant::Foo));
+        assert_eq!(Discriminant::Foo as u8, 3);
+        assert_eq!(Discriminant::Bar.to_string(), "four");
+        assert_eq!(Discriminant::Baz.to_string(), "Baz");
+    }
+
+    #[test]
+    fn repr_u8_round_trip() {
+        for (n, level) in Level::all_variants().iter().enumerate() {
+            assert_eq!(level.as_u8() as usize, n);
+            assert_eq!(Level::from_u8(level.as_u8()), Some(*level));
+        }
+        assert_eq!(Level::from_u8(3), None);
+    }
+
+    // `lint_hints` reports overlong hints as deprecation warnings.
+    #[allow(deprecated)]
+    mod lint_hints {
+        use rustfmt_config_proc_macro::config_type;
+
+        #[config_type(lint_hints)]
+        enum Verbose {
+            #[doc_hint = "a very long hint that goes well past forty characters"]
+            Long,
+            #[doc_hint = "short"]
+            Short,
+        }
+
+        #[test]
+        fn doc_hint_is_unchanged() {
+            use crate::config::ConfigType;
+
+            assert_eq!(
+                Verbose::doc_hint(),
+                "[a very long hint that goes well past forty characters|short]"
+            );
+        }
+    }
+
+    #[test]
+    fn doc_hint_const() {
+        use crate::config::ConfigType;
+
+        const DOC_HINT: &str = Qux::DOC_HINT;
+        assert_eq!(DOC_HINT, Qux::doc_hint());
+        assert_eq!(Bar::DOC_HINT, Bar::doc_hint());
+    }
+
+    #[test]
+    fn variant_count() {
+        assert_eq!(Bar::VARIANT_COUNT, 4);
+        assert_eq!(Qux::VARIANT_COUNT, 3);
+        assert_eq!(Baz::VARIANT_COUNT, Baz::all_variants().len());
+    }
+
+    #[test]
+    fn values() {
+        const VALUES: &[&str] = Color::VALUES;
+        assert_eq!(VALUES, &["always", "Never"]);
+        assert_eq!(Baz::VALUES.len(), Baz::all_variants().len());
+    }
+
+    #[test]
+    fn resolve_layers() {
+        assert_eq!(
+            Baz::resolve(&[None, Some(Baz::Bar), None], Baz::Foo),
+            Baz::Bar
+        );
+        assert_eq!(
+            Baz::resolve(&[Some(Baz::Baz), Some(Baz::Bar)], Baz::Foo),
+            Baz::Baz
+        );
+        assert_eq!(Baz::resolve(&[None, None, None], Baz::Foo), Baz::Foo);
+    }
+
+    #[test]
+    fn all_variants() {
+        assert_eq!(Baz::all_variants(), &[Baz::Foo, Baz::Bar, Baz::Baz]);
+    }
+
+    #[test]
+    fn into_static_str() {
+        let value: &'static str = Switch::On.into();
+        assert_eq!(value, "On");
+        assert_eq!(<&str>::from(&Switch::Off), "off");
+    }
+
+    #[test]
+    fn cycle_through_variants() {
+        assert_eq!(Baz::Foo.next(), Baz::Bar);
+        assert_eq!(Baz::Baz.next(), Baz::Foo);
+        assert_eq!(Baz::Foo.prev(), Baz::Baz);
+        assert_eq!(Baz::Bar.prev(), Baz::Foo);
+    }
+}
+
+#[cfg(feature = "serde")]
+mod deserialize {
+    use rustfmt_config_proc_macro::config_type;
+    use serde::de::value::{Error, StrDeserializer};
+    use serde::de::{Deserialize, IntoDeserializer};
+
+    #[config_type]
+    enum Style {
+        Unix,
+        #[value = "crlf"]
+        Windows,
+    }
+
+    fn deserialize(s: &str) -> Result<Style, Error> {
+        let d: StrDeserializer<'_, Error> = s.into_deserializer();
+        Style::deserialize(d)
+    }
+
+    #[test]
+    fn deserialize_from_value_string() {
+        assert_eq!(deserialize("Unix").unwrap(), Style::Unix);
+        assert_eq!(deserialize("unix").unwrap(), Style::Unix);
+        assert_eq!(deserialize("crlf").unwrap(), Style::Windows);
+        assert!(deserialize("Windows").is_err());
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary {
+    use arbitrary::{Arbitrary, Unstructured};
+    use rustfmt_config_proc_macro::config_type;
+
+    #[config_type]
+    enum Style {
+        Unix,
+        Windows,
+        Native,
+    }
+
+    #[test]
+    fn arbitrary_picks_a_variant() {
+        let data = [0, 1, 2, 3, 255];
+        let mut u = Unstructured::new(&data);
+        for _ in 0..data.len() {
+            let style = Style::arbitrary(&mut u).unwrap();
+            assert!(Style::all_variants().contains(&style));
+        }
+    }
 }
@@ -0,0 +1,6 @@
+use rustfmt_config_proc_macro::config_type;
+
+#[config_type]
+enum Empty {}
+
+fn main() {}
@@ -0,0 +1,20 @@
+#![deny(deprecated)]
+#![allow(unused_imports)]
+
+use rustfmt_config_proc_macro::config_type;
+
+mod config {
+    pub trait ConfigType: Sized {
+        fn doc_hint() -> String;
+    }
+}
+
+#[config_type(lint_hints)]
+enum Verbose {
+    #[doc_hint = "a very long hint that goes well past forty characters"]
+    Long,
+    #[doc_hint = "short"]
+    Short,
+}
+
+fn main() {}
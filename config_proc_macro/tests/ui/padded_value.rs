@@ -0,0 +1,10 @@
+use rustfmt_config_proc_macro::config_type;
+
+#[config_type]
+enum Style {
+    #[value = " Unix "]
+    Unix,
+    Windows,
+}
+
+fn main() {}
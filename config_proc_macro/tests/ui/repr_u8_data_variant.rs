@@ -0,0 +1,9 @@
+use rustfmt_config_proc_macro::config_type;
+
+#[config_type(repr_u8)]
+enum Data {
+    Unit,
+    Tuple(u8),
+}
+
+fn main() {}
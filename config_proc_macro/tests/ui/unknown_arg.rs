@@ -0,0 +1,8 @@
+use rustfmt_config_proc_macro::config_type;
+
+#[config_type(repr_u16)]
+enum Unknown {
+    Unit,
+}
+
+fn main() {}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::cell::Cell;
 use std::default::Default;
 use std::fs::File;
@@ -13,6 +14,7 @@ pub use crate::config::lists::*;
 pub use crate::config::options::*;
 
 use crate::config::config_type::ConfigType;
+use crate::formatting::syntux::session::ignore_path::IgnorePathSet;
 
 #[macro_use]
 pub mod config_type;
@@ -33,6 +35,8 @@ create_config! {
     hard_tabs: bool, false, true, "Use tab characters for indentation, spaces for alignment";
     tab_spaces: usize, 4, true, "Number of spaces per tab";
     newline_style: NewlineStyle, NewlineStyle::Auto, true, "Unix or Windows line endings";
+    final_newline: FinalNewline, FinalNewline::Ensure, false, "How to handle the newlines \
+        at the end of a file";
     indent_style: IndentStyle, IndentStyle::Block, false, "How do we indent expressions or items";
     width_heuristics: Heuristics, Heuristics::Scaled, true, "Controls width heuristics \
         by setting the values for the individual width heuristic options";
@@ -123,6 +127,8 @@ create_config! {
         "How to handle trailing commas for lists";
     match_block_trailing_comma: bool, false, true,
         "Put a trailing comma after a block based match arm (non-block arms are not affected)";
+    closing_comment_unindent: ClosingCommentUnindent, ClosingCommentUnindent::IfElse, false,
+        "Blocks whose comment before the closing brace may be unindented to the brace";
     blank_lines_upper_bound: usize, 1, false,
         "Maximum number of blank lines which can be put between items";
     blank_lines_lower_bound: usize, 0, false,
@@ -154,8 +160,14 @@ create_config! {
     error_on_unformatted: bool, false, false,
         "Error if unable to get comments or string literals within max_width, \
          or they are left with trailing whitespaces";
+    error_on_warnings: bool, false, false,
+        "Error if the original text had to be kept for a part of the input that failed to format";
     ignore: IgnoreList, IgnoreList::default(), true,
         "Skip formatting the specified files and directories";
+    force_format: ForceFormatList, ForceFormatList::default(), false,
+        "Format the specified files and directories even if they are ignored";
+    file_overrides: FileOverrides, FileOverrides::default(), false,
+        "Override options for the files matching the specified patterns";
 
     // Not user-facing
     file_lines: FileLines, FileLines::all(), false,
@@ -231,6 +243,60 @@ impl Config {
         true
     }
 
+    /// Returns a warning for each combination of options that produces degenerate output. Each
+    /// option is valid on its own, so these are not errors.
+    pub fn validate_interdependencies(&self) -> Vec<String> {
+        let mut warnings = vec![];
+        if self.max_width() < self.tab_spaces() {
+            warnings.push(format!(
+                "`max_width` ({}) is smaller than `tab_spaces` ({}), so every indented line \
+                 exceeds `max_width`",
+                self.max_width(),
+                self.tab_spaces(),
+            ));
+        }
+        if self.width_heuristics() == Heuristics::Off && self.fn_call_width() == 0 {
+            warnings.push(
+                "`fn_call_width = 0` with `width_heuristics = \"Off\"` puts every argument of \
+                 every function call on its own line"
+                    .to_owned(),
+            );
+        }
+        warnings
+    }
+
+    /// Returns `true` if the given file should be formatted under this config, i.e., it is not
+    /// excluded by the `ignore` option, or it is matched by the `force_format` option. Inputs
+    /// other than a real file are always formatted.
+    pub fn should_format_file(&self, file: &FileName) -> bool {
+        match IgnorePathSet::from_config(self) {
+            Ok(ignore_path_set) => !ignore_path_set.is_match(file),
+            // An invalid ignore list is reported when formatting starts.
+            Err(_) => true,
+        }
+    }
+
+    /// Returns the config to format `file` with, i.e., `self` with the options of every matching
+    /// `file_overrides` entry applied.
+    pub fn for_file(&self, file: &FileName) -> Cow<'_, Config> {
+        let file_overrides = self.file_overrides();
+        if file_overrides.is_empty() {
+            return Cow::Borrowed(self);
+        }
+
+        let mut config = self.clone();
+        for file_override in &file_overrides {
+            let is_match = IgnorePathSet::from_ignore_list(file_override.files())
+                .map_or(false, |path_set| path_set.is_match(file));
+            if is_match {
+                for (key, value) in file_override.option_values() {
+                    config.override_value(key, &value);
+                }
+            }
+        }
+        Cow::Owned(config)
+    }
+
     /// Constructs a `Config` from the toml file specified at `file_path`.
     ///
     /// This method only looks at the provided path, for a method that
@@ -332,6 +398,23 @@ pub fn load_config<O: CliOptions>(
     file_path: Option<&Path>,
     options: Option<&O>,
 ) -> Result<(Config, Option<Vec<PathBuf>>), Error> {
+    load_config_reporting(file_path, options).map(|(config, paths, ..)| (config, paths))
+}
+
+/// Like `load_config`, but also returns every option that was overridden by `options`, and the
+/// warnings of `Config::validate_interdependencies` for the resulting config.
+pub fn load_config_reporting<O: CliOptions>(
+    file_path: Option<&Path>,
+    options: Option<&O>,
+) -> Result<
+    (
+        Config,
+        Option<Vec<PathBuf>>,
+        Vec<ConfigOverride>,
+        Vec<String>,
+    ),
+    Error,
+> {
     let over_ride = match options {
         Some(opts) => config_path(opts)?,
         None => None,
@@ -346,10 +429,12 @@ pub fn load_config<O: CliOptions>(
     };
 
     result.map(|(mut c, p)| {
-        if let Some(options) = options {
-            options.apply_to(&mut c);
-        }
-        (c, p)
+        let overrides = match options {
+            Some(options) => options.apply_to_reporting(&mut c),
+            None => vec![],
+        };
+        let warnings = c.validate_interdependencies();
+        (c, p, overrides, warnings)
     })
 }
 
@@ -441,6 +526,8 @@ mod test {
             chain_width: usize, 60, true, "Maximum length of a chain to fit on a single line.";
             single_line_if_else_max_width: usize, 50, true, "Maximum line length for single \
                 line if-else expressions. A value of zero means always break if-else expressions.";
+            comment_width: usize, 80, false,
+                "Maximum length of comments. No effect unless wrap_comments = true";
 
             unstable_features: bool, false, true,
                 "Enables unstable features on stable and beta channels \
@@ -488,6 +575,72 @@ mod test {
         }
     }
 
+    struct MaxWidthOptions(usize);
+
+    impl CliOptions for MaxWidthOptions {
+        fn apply_to(&self, config: &mut Config) {
+            config.set().max_width(self.0);
+        }
+        fn config_path(&self) -> Option<&Path> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_apply_to_reporting() {
+        let mut config = Config::default();
+        let overrides = MaxWidthOptions(120).apply_to_reporting(&mut config);
+        assert_eq!(
+            overrides,
+            vec![("max_width", "100".to_owned(), "120".to_owned())]
+        );
+
+        let overrides = MaxWidthOptions(120).apply_to_reporting(&mut config);
+        assert!(overrides.is_empty());
+    }
+
+    #[test]
+    fn test_non_default_options() {
+        let mut config = Config::default();
+        assert!(config.non_default_options().is_empty());
+
+        config.set().hard_tabs(true);
+        config.set().max_width(100);
+        assert_eq!(
+            config.non_default_options(),
+            vec![("hard_tabs", "true".to_owned())]
+        );
+    }
+
+    #[test]
+    fn test_non_default_options_skips_derived_options() {
+        let mut config = Config::default();
+        config.set().max_width(120);
+        assert_ne!(config.fn_call_width(), Config::default().fn_call_width());
+        assert_eq!(
+            config.non_default_options(),
+            vec![("max_width", "120".to_owned())]
+        );
+    }
+
+    #[test]
+    fn test_reset_option() {
+        let mut config = Config::default();
+        config.set().hard_tabs(true);
+        config.set().max_width(80);
+        assert!(config.reset_option("hard_tabs").is_ok());
+        assert!(config.reset_option("max_width").is_ok());
+
+        let default_config = Config::default();
+        assert_eq!(config.hard_tabs(), default_config.hard_tabs());
+        assert!(!config.was_set().hard_tabs());
+        assert_eq!(config.max_width(), default_config.max_width());
+        assert_eq!(config.fn_call_width(), default_config.fn_call_width());
+        assert!(config.non_default_options().is_empty());
+
+        assert!(config.reset_option("no_such_option").is_err());
+    }
+
     #[test]
     fn test_config_set() {
         let mut config = Config::default();
@@ -544,6 +697,20 @@ mod test {
         assert_eq!(s.contains("(unstable)"), true);
     }
 
+    #[test]
+    fn test_option_names_and_doc_for() {
+        use self::mock::Config;
+
+        let names = Config::option_names();
+        assert_eq!(names.first(), Some(&"max_width"));
+        assert!(names.contains(&"stable_option"));
+        assert!(names.contains(&"unstable_option"));
+
+        let config = Config::default();
+        assert_eq!(config.doc_for("stable_option"), Some("A stable option"));
+        assert_eq!(config.doc_for("dummy"), None);
+    }
+
     #[test]
     fn test_empty_string_license_template_path() {
         let toml = r#"license_template_path = """#;
@@ -568,6 +735,7 @@ mod test {
 hard_tabs = false
 tab_spaces = 4
 newline_style = "Auto"
+final_newline = "Ensure"
 indent_style = "Block"
 width_heuristics = "Scaled"
 fn_call_width = 60
@@ -617,6 +785,7 @@ control_brace_style = "AlwaysSameLine"
 trailing_semicolon = true
 trailing_comma = "Vertical"
 match_block_trailing_comma = false
+closing_comment_unindent = "IfElse"
 blank_lines_upper_bound = 1
 blank_lines_lower_bound = 0
 edition = "2018"
@@ -633,7 +802,10 @@ unstable_features = false
 hide_parse_errors = false
 error_on_line_overflow = false
 error_on_unformatted = false
+error_on_warnings = false
 ignore = []
+force_format = []
+file_overrides = []
 "#,
             env!("CARGO_PKG_VERSION")
         );
@@ -641,6 +813,95 @@ ignore = []
         assert_eq!(&toml, &default_config);
     }
 
+    #[test]
+    fn test_should_format_file() {
+        let config = Config::from_toml(r#"ignore = ["foo.rs", "gen/*"]"#, Path::new("")).unwrap();
+
+        assert!(!config.should_format_file(&FileName::Real(PathBuf::from("foo.rs"))));
+        assert!(!config.should_format_file(&FileName::Real(PathBuf::from("gen/api.rs"))));
+        assert!(config.should_format_file(&FileName::Real(PathBuf::from("src/lib.rs"))));
+        assert!(config.should_format_file(&FileName::Stdin));
+    }
+
+    #[test]
+    fn test_should_format_file_with_force_format() {
+        if !crate::is_nightly_channel!() {
+            // `force_format` is unstable
+            return;
+        }
+        let toml = r#"
+ignore = ["target/"]
+force_format = ["target/gen/api.rs"]
+"#;
+        let config = Config::from_toml(toml, Path::new("")).unwrap();
+
+        assert!(config.should_format_file(&FileName::Real(PathBuf::from("target/gen/api.rs"))));
+        assert!(!config.should_format_file(&FileName::Real(PathBuf::from("target/gen/lib.rs"))));
+        assert!(config.should_format_file(&FileName::Real(PathBuf::from("src/lib.rs"))));
+    }
+
+    #[test]
+    fn test_validate_interdependencies() {
+        let config = Config::from_toml(
+            r#"
+max_width = 3
+width_heuristics = "Off"
+fn_call_width = 0
+"#,
+            Path::new(""),
+        )
+        .unwrap();
+        let warnings = config.validate_interdependencies();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].starts_with("`max_width` (3) is smaller than `tab_spaces` (4)"));
+        assert!(warnings[1].starts_with("`fn_call_width = 0` with `width_heuristics = \"Off\"`"));
+
+        let config = Config::from_toml("width_heuristics = \"Off\"", Path::new("")).unwrap();
+        assert!(config.validate_interdependencies().is_empty());
+        assert!(Config::default().validate_interdependencies().is_empty());
+    }
+
+    #[test]
+    fn test_for_file() {
+        if !crate::is_nightly_channel!() {
+            // `file_overrides` is unstable
+            return;
+        }
+        let toml = r#"
+max_width = 100
+
+[[file_overrides]]
+files = ["gen/*"]
+max_width = 120
+hard_tabs = true
+
+[[file_overrides]]
+files = ["gen/api.rs"]
+max_width = 110
+"#;
+        let config = Config::from_toml(toml, Path::new("")).unwrap();
+
+        let lib_config = config.for_file(&FileName::Real(PathBuf::from("src/lib.rs")));
+        assert_eq!(lib_config.max_width(), 100);
+        assert_eq!(lib_config.hard_tabs(), false);
+        let types_config = config.for_file(&FileName::Real(PathBuf::from("gen/types.rs")));
+        assert_eq!(types_config.max_width(), 120);
+        assert_eq!(types_config.hard_tabs(), true);
+        let api_config = config.for_file(&FileName::Real(PathBuf::from("gen/api.rs")));
+        assert_eq!(api_config.max_width(), 110);
+        assert_eq!(api_config.hard_tabs(), true);
+    }
+
+    #[test]
+    fn test_invalid_file_overrides() {
+        let toml = r#"
+[[file_overrides]]
+files = ["gen/*"]
+max_width = "wide"
+"#;
+        assert!(Config::from_toml(toml, Path::new("")).is_err());
+    }
+
     #[test]
     fn test_merged_config() {
         match option_env!("CFG_RELEASE_CHANNEL") {
@@ -812,6 +1073,29 @@ ignore = []
             assert_eq!(config.single_line_if_else_max_width(), 100);
             assert_eq!(config.struct_lit_width(), 36);
             assert_eq!(config.struct_variant_width(), 70);
+            assert_eq!(config.comment_width(), 160);
+        }
+
+        #[test]
+        fn test_effective_width_heuristics() {
+            let config = Config::from_toml("max_width = 120", Path::new("")).unwrap();
+            assert_eq!(
+                config.effective_width_heuristics(),
+                WidthHeuristics::scaled(120)
+            );
+
+            let toml = r#"
+                max_width = 120
+                fn_call_width = 100
+            "#;
+            let config = Config::from_toml(toml, Path::new("")).unwrap();
+            assert_eq!(
+                config.effective_width_heuristics(),
+                WidthHeuristics {
+                    fn_call_width: 100,
+                    ..WidthHeuristics::scaled(120)
+                }
+            );
         }
 
         #[test]
@@ -828,6 +1112,7 @@ ignore = []
             assert_eq!(config.single_line_if_else_max_width(), 120);
             assert_eq!(config.struct_lit_width(), 120);
             assert_eq!(config.struct_variant_width(), 120);
+            assert_eq!(config.comment_width(), 120);
         }
 
         #[test]
@@ -844,6 +1129,7 @@ ignore = []
             assert_eq!(config.single_line_if_else_max_width(), 0);
             assert_eq!(config.struct_lit_width(), 0);
             assert_eq!(config.struct_variant_width(), 0);
+            assert_eq!(config.comment_width(), 80);
         }
 
         #[test]
@@ -1030,5 +1316,28 @@ ignore = []
             config.override_value("single_line_if_else_max_width", "101");
             assert_eq!(config.single_line_if_else_max_width(), 100);
         }
+
+        #[test]
+        fn test_single_line_if_else_max_width_sentinels() {
+            let mut config = Config::default();
+            config.override_value("single_line_if_else_max_width", "0");
+            assert_eq!(
+                config.single_line_if_else_max_width(),
+                WidthHeuristics::ALWAYS_BREAK
+            );
+
+            let always_one_line = WidthHeuristics::ALWAYS_ONE_LINE.to_string();
+            config.override_value("single_line_if_else_max_width", &always_one_line);
+            assert_eq!(
+                config.single_line_if_else_max_width(),
+                WidthHeuristics::ALWAYS_ONE_LINE
+            );
+
+            config.override_value("max_width", "80");
+            assert_eq!(
+                config.single_line_if_else_max_width(),
+                WidthHeuristics::ALWAYS_ONE_LINE
+            );
+        }
     }
 }
@@ -1,5 +1,5 @@
 use crate::config::file_lines::FileLines;
-use crate::config::options::{IgnoreList, WidthHeuristics};
+use crate::config::options::{FileOverrides, ForceFormatList, IgnoreList, WidthHeuristics};
 
 /// Trait for types that can be used in `Config`.
 pub(crate) trait ConfigType: Sized {
@@ -50,6 +50,18 @@ impl ConfigType for IgnoreList {
     }
 }
 
+impl ConfigType for ForceFormatList {
+    fn doc_hint() -> String {
+        String::from("[<string>,..]")
+    }
+}
+
+impl ConfigType for FileOverrides {
+    fn doc_hint() -> String {
+        String::from("[{ files = [<string>,..], <option> = <value>,.. },..]")
+    }
+}
+
 macro_rules! update_config {
     ($config:ident, ignore = $val:ident, $dir:ident) => {
         $config.ignore.1 = true;
@@ -60,6 +72,24 @@ macro_rules! update_config {
         $config.ignore.2 = old_ignored.merge_into(new_ignored);
     };
 
+    ($config:ident, force_format = $val:ident, $dir:ident) => {
+        $config.force_format.1 = true;
+
+        let mut new_forced = $val;
+        new_forced.add_prefix($dir);
+        let old_forced = $config.force_format.2;
+        $config.force_format.2 = old_forced.merge_into(new_forced);
+    };
+
+    ($config:ident, file_overrides = $val:ident, $dir:ident) => {
+        $config.file_overrides.1 = true;
+
+        let mut new_overrides = $val;
+        new_overrides.add_prefix($dir);
+        let old_overrides = $config.file_overrides.2;
+        $config.file_overrides.2 = old_overrides.merge_into(new_overrides);
+    };
+
     ($config:ident, $i:ident = $val:ident, $dir:ident) => {
         $config.$i.1 = true;
         $config.$i.2 = $val;
@@ -119,7 +149,8 @@ macro_rules! create_config {
                         | "struct_lit_width"
                         | "struct_variant_width"
                         | "array_width"
-                        | "chain_width" => self.0.set_heuristics(),
+                        | "chain_width"
+                        | "comment_width" => self.0.set_heuristics(),
                         "license_template_path" => self.0.set_license_template(),
                         &_ => (),
                     }
@@ -270,12 +301,94 @@ macro_rules! create_config {
                     | "struct_lit_width"
                     | "struct_variant_width"
                     | "array_width"
-                    | "chain_width" => self.set_heuristics(),
+                    | "chain_width"
+                    | "comment_width" => self.set_heuristics(),
                     "license_template_path" => self.set_license_template(),
                     &_ => (),
                 }
             }
 
+            /// Resets the option `name` to its default, as if it had never been set.
+            #[allow(unreachable_pub)]
+            pub fn reset_option(&mut self, name: &str) -> Result<(), String> {
+                match name {
+                    $(
+                        stringify!($i) => {
+                            self.$i.1 = false;
+                            self.$i.2 = $def;
+                        }
+                    )+
+                    _ => return Err(format!("unknown config option `{}`", name)),
+                }
+
+                match name {
+                    "max_width"
+                    | "width_heuristics"
+                    | "fn_call_width"
+                    | "single_line_if_else_max_width"
+                    | "attr_fn_like_width"
+                    | "struct_lit_width"
+                    | "struct_variant_width"
+                    | "array_width"
+                    | "chain_width"
+                    | "comment_width" => self.set_heuristics(),
+                    "license_template_path" => self.license_template = None,
+                    &_ => (),
+                }
+                Ok(())
+            }
+
+            /// Returns every option whose value in `other` differs from the one in `self`.
+            #[allow(unreachable_pub)]
+            pub fn changed_options(&self, other: &Config) -> Vec<ConfigOverride> {
+                let mut changed = vec![];
+                $(
+                    if self.$i.2 != other.$i.2 {
+                        changed.push((
+                            stringify!($i),
+                            self.$i.2.to_string(),
+                            other.$i.2.to_string(),
+                        ));
+                    }
+                )+
+                changed
+            }
+
+            /// Returns the name and the value of every option whose value differs from its
+            /// default, e.g., for printing a minimal config. Options that were not set, but
+            /// derived from other ones, e.g., `fn_call_width` from `max_width`, are left out.
+            #[allow(unreachable_pub)]
+            pub fn non_default_options(&self) -> Vec<(&'static str, String)> {
+                let default = Config::default();
+                let mut options = vec![];
+                $(
+                    if self.$i.1 && self.$i.2 != default.$i.2 {
+                        options.push((stringify!($i), self.$i.2.to_string()));
+                    }
+                )+
+                options
+            }
+
+            /// Returns the name of every config option, in the order they are declared.
+            #[allow(unreachable_pub)]
+            pub fn option_names() -> &'static [&'static str] {
+                &[$(
+                    stringify!($i),
+                )+]
+            }
+
+            /// Returns the description of the config option `name`, or `None` if there is no such
+            /// option.
+            #[allow(unreachable_pub)]
+            pub fn doc_for(&self, name: &str) -> Option<&'static str> {
+                match name {
+                    $(
+                        stringify!($i) => Some($dstring),
+                    )+
+                        _ => None,
+                }
+            }
+
             #[allow(unreachable_pub)]
             pub fn is_hidden_option(name: &str) -> bool {
                 const HIDE_OPTIONS: [&str; 1] = [
@@ -332,6 +445,11 @@ macro_rules! create_config {
                     if !was_set {
                         return heuristic_value;
                     }
+                    if config_key == "single_line_if_else_max_width"
+                        && override_value == WidthHeuristics::ALWAYS_ONE_LINE
+                    {
+                        return override_value;
+                    }
                     if override_value > max_width {
                         eprintln!(
                             "`{0}` cannot have a value that exceeds `max_width`. \
@@ -398,6 +516,14 @@ macro_rules! create_config {
                     "single_line_if_else_max_width",
                 );
                 self.single_line_if_else_max_width.2 = single_line_if_else_max_width;
+
+                let comment_width = get_width_value(
+                    self.was_set().comment_width(),
+                    self.comment_width.2,
+                    heuristics.comment_width,
+                    "comment_width",
+                );
+                self.comment_width.2 = comment_width;
             }
 
             fn set_heuristics(&mut self) {
@@ -410,6 +536,22 @@ macro_rules! create_config {
                 };
             }
 
+            /// Returns the width heuristics used for formatting, i.e., the ones derived from
+            /// `width_heuristics` and `max_width`, with any explicitly set widths applied.
+            #[allow(unreachable_pub)]
+            pub fn effective_width_heuristics(&self) -> WidthHeuristics {
+                WidthHeuristics {
+                    fn_call_width: self.fn_call_width.2,
+                    attr_fn_like_width: self.attr_fn_like_width.2,
+                    struct_lit_width: self.struct_lit_width.2,
+                    struct_variant_width: self.struct_variant_width.2,
+                    array_width: self.array_width.2,
+                    chain_width: self.chain_width.2,
+                    single_line_if_else_max_width: self.single_line_if_else_max_width.2,
+                    comment_width: self.comment_width.2,
+                }
+            }
+
             fn set_license_template(&mut self) {
                 if self.was_set().license_template_path() {
                     let lt_path = self.license_template_path();
@@ -20,6 +20,9 @@ pub struct LineRange {
 }
 
 /// Defines the name of an input - either a file or stdin.
+///
+/// Real paths are ordered lexicographically and always come before stdin; emitters rely on
+/// this ordering to produce deterministic output.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub enum FileName {
     Real(PathBuf),
@@ -1,5 +1,8 @@
-use std::collections::{hash_set, HashSet};
+use std::collections::{hash_set, BTreeMap, HashSet};
+use std::convert::TryFrom;
 use std::fmt;
+use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
 
 use rustfmt_config_proc_macro::config_type;
@@ -8,7 +11,8 @@ use serde::ser::SerializeSeq;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::config::lists::*;
-use crate::config::Config;
+use crate::config::{Config, FileName};
+use crate::formatting::syntux::session::ignore_path::IgnorePathSet;
 
 #[config_type]
 pub enum NewlineStyle {
@@ -28,6 +32,48 @@ impl Default for NewlineStyle {
     }
 }
 
+/// Converts the `\n`-delimited `text` to `style`, so that tests can spell out expected line
+/// endings without writing `\r\n` by hand. `Auto` and `Native` use the platform's newlines, which
+/// is what `Auto` falls back to for input without newlines.
+#[cfg(test)]
+pub(crate) fn with_style(text: &str, style: NewlineStyle) -> String {
+    let windows = match style {
+        NewlineStyle::Windows => true,
+        NewlineStyle::Unix => false,
+        NewlineStyle::Auto | NewlineStyle::Native => cfg!(windows),
+    };
+    if windows {
+        text.replace('\n', "\r\n")
+    } else {
+        text.to_owned()
+    }
+}
+
+/// How to handle the newlines at the end of a file.
+#[config_type]
+pub enum FinalNewline {
+    /// End the file with exactly one newline.
+    Ensure,
+    /// Remove all the newlines at the end of the file.
+    Remove,
+    /// Leave the newlines at the end of the file as they are.
+    Preserve,
+}
+
+impl FinalNewline {
+    /// Enforces this policy on the end of the formatted `text`.
+    pub fn apply(self, text: &mut String) {
+        match self {
+            FinalNewline::Ensure => {
+                text.truncate(text.trim_end_matches('\n').len());
+                text.push('\n');
+            }
+            FinalNewline::Remove => text.truncate(text.trim_end_matches('\n').len()),
+            FinalNewline::Preserve => {}
+        }
+    }
+}
+
 #[config_type]
 /// Where to put the opening brace of items (`fn`, `impl`, etc.).
 pub enum BraceStyle {
@@ -51,6 +97,58 @@ pub enum ControlBraceStyle {
     AlwaysNextLine,
 }
 
+/// A control flow construct whose opening brace is placed according to `ControlBraceStyle`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ControlConstruct {
+    If,
+    IfLet,
+    While,
+    WhileLet,
+    Loop,
+    For,
+    Match,
+}
+
+impl ControlBraceStyle {
+    /// Returns the style to use for `construct`. Every construct currently uses `self`; this is
+    /// the place to add per-construct overrides.
+    pub fn for_construct(self, _construct: ControlConstruct) -> ControlBraceStyle {
+        self
+    }
+}
+
+#[config_type]
+/// Which blocks may keep a comment before their closing brace at the indentation of the brace,
+/// so that it reads as a comment on the code after the brace.
+pub enum ClosingCommentUnindent {
+    /// Blocks of `if` expressions followed by `else`.
+    IfElse,
+    /// Blocks of `if` expressions followed by `else`, and of match arms followed by another arm.
+    IfElseAndMatchArms,
+}
+
+/// Returns the `brace_style` and `control_brace_style` of a well-known brace style, i.e., `kr`,
+/// `allman` or `stroustrup`. The name is case-insensitive.
+///
+/// This is library API only; neither rustfmt.toml nor the command line accept a style name yet.
+pub fn from_named_style(name: &str) -> Option<(BraceStyle, ControlBraceStyle)> {
+    match name.to_ascii_lowercase().as_str() {
+        "kr" => Some((
+            BraceStyle::AlwaysNextLine,
+            ControlBraceStyle::AlwaysSameLine,
+        )),
+        "allman" => Some((
+            BraceStyle::AlwaysNextLine,
+            ControlBraceStyle::AlwaysNextLine,
+        )),
+        "stroustrup" => Some((
+            BraceStyle::AlwaysNextLine,
+            ControlBraceStyle::ClosingNextLine,
+        )),
+        _ => None,
+    }
+}
+
 #[config_type]
 /// How to indent.
 pub enum IndentStyle {
@@ -104,6 +202,19 @@ impl Density {
             Density::Vertical => ListTactic::Vertical,
         }
     }
+
+    /// Whether a list laid out with the given tactic should end with a trailing comma.
+    pub fn wants_trailing_comma(self, tactic: ListTactic) -> bool {
+        match tactic {
+            ListTactic::Vertical => true,
+            ListTactic::Horizontal => false,
+            // The final layout is not known yet, so only a vertical density implies that the
+            // list will end up one item per line.
+            ListTactic::HorizontalVertical
+            | ListTactic::LimitedHorizontalVertical(..)
+            | ListTactic::Mixed => self == Density::Vertical,
+        }
+    }
 }
 
 #[config_type]
@@ -113,6 +224,55 @@ pub enum ReportTactic {
     Never,
 }
 
+impl ReportTactic {
+    /// Returns `true` if the given `TODO` or `FIXME` comment should be reported.
+    pub fn should_report(self, comment: &str) -> bool {
+        match self {
+            ReportTactic::Always => true,
+            ReportTactic::Unnumbered => !has_issue_reference(comment),
+            ReportTactic::Never => false,
+        }
+    }
+}
+
+/// Returns `true` if the given comment refers to an issue, either by its number (`#123`),
+/// by a tracker key (`ISSUE-123`) or by a URL.
+///
+/// A tracker key is an uppercase project key followed by a number. Names of standards such as
+/// `UTF-8` or `SHA-1` have the same shape, so their common prefixes are never treated as keys.
+///
+/// This is library API only: rustfmt does not report `TODO` or `FIXME` comments itself.
+pub fn has_issue_reference(comment: &str) -> bool {
+    comment
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ')' || c == '[' || c == ']')
+        .map(|word| word.trim_end_matches(|c: char| c == ':' || c == ',' || c == '.'))
+        .any(is_issue_reference)
+}
+
+fn is_issue_reference(word: &str) -> bool {
+    const NON_TRACKER_KEYS: &[&str] = &["CRC", "ISO", "SHA", "UCS", "UTF"];
+    let is_number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+
+    if word.starts_with("http://") || word.starts_with("https://") {
+        return true;
+    }
+    if let Some(number) = word.strip_prefix('#') {
+        return is_number(number);
+    }
+    match word.rfind('-') {
+        Some(pos) => {
+            let (key, number) = (&word[..pos], &word[pos + 1..]);
+            let mut key_chars = key.chars();
+            key.len() >= 2
+                && key_chars.next().map_or(false, |c| c.is_ascii_uppercase())
+                && key_chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+                && !NON_TRACKER_KEYS.contains(&key)
+                && is_number(number)
+        }
+        None => false,
+    }
+}
+
 #[config_type]
 /// rustfmt format style version.
 pub enum Version {
@@ -122,7 +282,7 @@ pub enum Version {
     Two,
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct WidthHeuristics {
     // Maximum width of the args of a function call before falling back
     // to vertical formatting.
@@ -142,8 +302,11 @@ pub struct WidthHeuristics {
     // Maximum length of a chain to fit on a single line.
     pub chain_width: usize,
     // Maximum line length for single line if-else expressions. A value
-    // of zero means always break if-else expressions.
+    // of zero (`ALWAYS_BREAK`) means always break if-else expressions, while
+    // `ALWAYS_ONE_LINE` keeps them on one line whenever they fit in `max_width`.
     pub single_line_if_else_max_width: usize,
+    // Maximum length of comments. No effect unless wrap_comments = true.
+    pub comment_width: usize,
 }
 
 impl fmt::Display for WidthHeuristics {
@@ -153,7 +316,14 @@ impl fmt::Display for WidthHeuristics {
 }
 
 impl WidthHeuristics {
-    // Using this WidthHeuristics means we ignore heuristics.
+    /// A `single_line_if_else_max_width` that always breaks if-else expressions.
+    pub const ALWAYS_BREAK: usize = 0;
+    /// A `single_line_if_else_max_width` that never breaks an if-else expression which fits in
+    /// `max_width`. Unlike other widths, it is not capped by `max_width`.
+    pub const ALWAYS_ONE_LINE: usize = usize::MAX;
+
+    // Using this WidthHeuristics means we ignore heuristics. If-else expressions always break
+    // (`ALWAYS_BREAK`).
     pub fn null() -> WidthHeuristics {
         WidthHeuristics {
             fn_call_width: usize::max_value(),
@@ -162,10 +332,13 @@ impl WidthHeuristics {
             struct_variant_width: 0,
             array_width: usize::max_value(),
             chain_width: usize::max_value(),
-            single_line_if_else_max_width: 0,
+            single_line_if_else_max_width: Self::ALWAYS_BREAK,
+            // Comments keep the default width rather than growing unbounded.
+            comment_width: 80,
         }
     }
 
+    // Every width is `max_width`, so no sentinel is used.
     pub fn set(max_width: usize) -> WidthHeuristics {
         WidthHeuristics {
             fn_call_width: max_width,
@@ -175,12 +348,16 @@ impl WidthHeuristics {
             array_width: max_width,
             chain_width: max_width,
             single_line_if_else_max_width: max_width,
+            comment_width: max_width,
         }
     }
 
-    // scale the default WidthHeuristics according to max_width
+    // scale the default WidthHeuristics according to max_width. No sentinel is used.
     pub fn scaled(max_width: usize) -> WidthHeuristics {
         const DEFAULT_MAX_WIDTH: usize = 100;
+        // A scaled width never drops below this, so that a small default cannot round down to
+        // zero and force everything to be formatted vertically.
+        const MIN_SCALED_WIDTH: usize = 1;
         let max_width_ratio = if max_width > DEFAULT_MAX_WIDTH {
             let ratio = max_width as f32 / DEFAULT_MAX_WIDTH as f32;
             // round to the closest 0.1
@@ -188,14 +365,17 @@ impl WidthHeuristics {
         } else {
             1.0
         };
+        let scale = |width: f32| ((width * max_width_ratio).round() as usize).max(MIN_SCALED_WIDTH);
         WidthHeuristics {
-            fn_call_width: (60.0 * max_width_ratio).round() as usize,
-            attr_fn_like_width: (70.0 * max_width_ratio).round() as usize,
-            struct_lit_width: (18.0 * max_width_ratio).round() as usize,
-            struct_variant_width: (35.0 * max_width_ratio).round() as usize,
-            array_width: (60.0 * max_width_ratio).round() as usize,
-            chain_width: (60.0 * max_width_ratio).round() as usize,
+            fn_call_width: scale(60.0),
+            attr_fn_like_width: scale(70.0),
+            struct_lit_width: scale(18.0),
+            struct_variant_width: scale(35.0),
+            array_width: scale(60.0),
+            chain_width: scale(60.0),
+            // Not floored, since zero means always breaking if-else expressions.
             single_line_if_else_max_width: (50.0 * max_width_ratio).round() as usize,
+            comment_width: scale(80.0),
         }
     }
 }
@@ -277,6 +457,83 @@ impl IgnoreList {
         &self.rustfmt_toml_path
     }
 
+    /// Reads the patterns of a `.gitignore` file at `path`. Blank lines, comments and unescaped
+    /// trailing spaces are dropped, and every other line is kept as is, so the matcher interprets
+    /// escapes such as `\#` and `\!` and negations with `!` like git does. The patterns are
+    /// relative to the directory that contains the file.
+    ///
+    /// `Config` never calls this; the list only takes effect where a caller uses it, e.g., by
+    /// merging it into the `ignore` option.
+    pub fn from_gitignore(path: &Path) -> io::Result<IgnoreList> {
+        // A trailing space is only kept when it is escaped with `\`.
+        fn trim_end(line: &str) -> &str {
+            let trimmed = line.trim_end();
+            if trimmed.ends_with('\\') && trimmed.len() < line.len() {
+                &line[..=trimmed.len()]
+            } else {
+                trimmed
+            }
+        }
+
+        let path_set = fs::read_to_string(path)?
+            .lines()
+            .map(trim_end)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(PathBuf::from)
+            .collect();
+        Ok(IgnoreList {
+            path_set,
+            rustfmt_toml_path: path.to_path_buf(),
+        })
+    }
+
+    /// Returns `true` if `module_path` (e.g., `["crate", "generated", "api"]`) is inside a module
+    /// listed as a `::`-separated path (e.g., `crate::generated`). Entries without `::` are file
+    /// paths and never match. The leading `crate` segment is optional on both sides.
+    ///
+    /// This is library API only: rustfmt itself skips files by path and does not query module
+    /// paths, so a `::`-separated entry has no effect on formatting.
+    pub fn skip_module(&self, module_path: &[&str]) -> bool {
+        fn strip_crate<'a, 'b>(segments: &'b [&'a str]) -> &'b [&'a str] {
+            match segments.split_first() {
+                Some((&"crate", rest)) => rest,
+                _ => segments,
+            }
+        }
+
+        let module_path = strip_crate(module_path);
+        self.path_set
+            .iter()
+            .filter_map(|entry| entry.to_str())
+            .filter(|entry| entry.contains("::"))
+            .any(|entry| {
+                let entry = entry.split("::").collect::<Vec<_>>();
+                let entry = strip_crate(&entry);
+                !entry.is_empty() && module_path.starts_with(entry)
+            })
+    }
+
+    /// Returns the entry that causes `path` to be skipped, or `None` if no entry covers it. `path`
+    /// is matched the same way as when formatting, i.e., as a `.gitignore` pattern relative to the
+    /// directory that contains rustfmt.toml. If several entries cover `path`, the most specific
+    /// one, i.e., the one matching the deepest component of `path`, is returned.
+    pub fn skip_reason(&self, path: &Path) -> Option<PathBuf> {
+        let ignore_path_set = IgnorePathSet::from_ignore_list(self).ok()?;
+        ignore_path_set
+            .list_matches(&FileName::Real(path.to_path_buf()))
+            .ignore
+            .map(PathBuf::from)
+    }
+
+    /// Returns `true` if `line` (1-based) of `file` is inside a range ignored by an entry of the
+    /// form `path:start-end`, e.g., `src/generated.rs:10-20`. The range is inclusive, and `path`
+    /// is relative to the directory that contains rustfmt.toml, like the other entries.
+    pub fn skip_range(&self, file: &FileName, line: usize) -> bool {
+        IgnorePathSet::from_ignore_list(self).map_or(false, |ignore_path_set| {
+            ignore_path_set.skip_lines(file, line, line)
+        })
+    }
+
     /// Merges `self` into `other`, returning a new `IgnoreList`. The resulting `IgnoreList` uses
     /// the `rustfmt_toml_path` of `other`, and only contains paths that are in `other`'s
     /// `rustfmt_toml_path`.
@@ -305,19 +562,214 @@ impl IgnoreList {
     }
 }
 
+/// Splits an `IgnoreList` entry of the form `path:start-end` into its path and line range, or
+/// returns `None` if the entry is a plain path.
+pub(crate) fn line_range_entry(entry: &Path) -> Option<(&Path, usize, usize)> {
+    let entry = entry.to_str()?;
+    let colon = entry.rfind(':')?;
+    let mut range = entry[colon + 1..].splitn(2, '-');
+    let start = range.next()?.parse().ok()?;
+    let end = range.next()?.parse().ok()?;
+    Some((Path::new(&entry[..colon]), start, end))
+}
+
 impl std::str::FromStr for IgnoreList {
     type Err = &'static str;
 
+    /// Parses a comma- or semicolon-separated list of paths, e.g., `gen/,vendor/`. Whitespace
+    /// around each path is trimmed and empty entries are skipped.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let path_set = s
+            .split(|c| c == ',' || c == ';')
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .collect();
+        Ok(IgnoreList {
+            path_set,
+            rustfmt_toml_path: PathBuf::new(),
+        })
+    }
+}
+
+/// A set of directories and files that rustfmt should format even when they are matched by
+/// `ignore`.
+#[derive(Default, Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct ForceFormatList(IgnoreList);
+
+impl fmt::Display for ForceFormatList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl AsRef<IgnoreList> for ForceFormatList {
+    fn as_ref(&self) -> &IgnoreList {
+        &self.0
+    }
+}
+
+impl ForceFormatList {
+    pub fn add_prefix(&mut self, dir: &Path) {
+        self.0.add_prefix(dir);
+    }
+
+    /// Merges `self` into `other`, following the same rules as `IgnoreList::merge_into`.
+    pub fn merge_into(self, other: Self) -> Self {
+        ForceFormatList(self.0.merge_into(other.0))
+    }
+}
+
+impl std::str::FromStr for ForceFormatList {
+    type Err = &'static str;
+
+    fn from_str(_: &str) -> Result<Self, Self::Err> {
+        Err("ForceFormatList is not parsable")
+    }
+}
+
+/// A set of options that only apply to the files matching `files`.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "RawFileOverride")]
+pub struct FileOverride {
+    /// Patterns of the files this override applies to, in the same format as `ignore`.
+    files: IgnoreList,
+    #[serde(flatten)]
+    options: BTreeMap<String, toml::Value>,
+}
+
+#[derive(Deserialize)]
+struct RawFileOverride {
+    files: IgnoreList,
+    #[serde(flatten)]
+    options: BTreeMap<String, toml::Value>,
+}
+
+impl TryFrom<RawFileOverride> for FileOverride {
+    type Error = String;
+
+    fn try_from(raw: RawFileOverride) -> Result<Self, Self::Error> {
+        let file_override = FileOverride {
+            files: raw.files,
+            options: raw.options,
+        };
+        for (key, value) in file_override.option_values() {
+            if !Config::is_valid_key_val(key, &value) {
+                return Err(format!(
+                    "invalid option in file_overrides: `{} = {}`",
+                    key, value
+                ));
+            }
+        }
+        Ok(file_override)
+    }
+}
+
+impl FileOverride {
+    pub fn files(&self) -> &IgnoreList {
+        &self.files
+    }
+
+    /// Returns each option along with its value, formatted so that `Config::override_value`
+    /// can parse it.
+    pub fn option_values(&self) -> impl Iterator<Item = (&str, String)> {
+        self.options.iter().map(|(key, value)| {
+            let value = match value {
+                toml::Value::String(s) => s.clone(),
+                value => value.to_string(),
+            };
+            (key.as_str(), value)
+        })
+    }
+}
+
+/// Per-file option overrides. When several overrides match a file, they are applied in order,
+/// so later ones take precedence.
+#[derive(Default, Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct FileOverrides(Vec<FileOverride>);
+
+/// Renders the overrides as `[[file_overrides]]` tables, like they are written in rustfmt.toml.
+impl fmt::Display for FileOverrides {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "[]");
+        }
+        for (i, file_override) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let mut files = file_override
+                .files
+                .into_iter()
+                .map(|path| path.to_string_lossy().into_owned())
+                .collect::<Vec<_>>();
+            files.sort();
+            let files = files.into_iter().map(toml::Value::String).collect();
+            write!(
+                f,
+                "[[file_overrides]]\nfiles = {}",
+                toml::Value::Array(files)
+            )?;
+            for (key, value) in &file_override.options {
+                write!(f, "\n{} = {}", key, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> IntoIterator for &'a FileOverrides {
+    type Item = &'a FileOverride;
+    type IntoIter = std::slice::Iter<'a, FileOverride>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl FileOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn add_prefix(&mut self, dir: &Path) {
+        for file_override in &mut self.0 {
+            file_override.files.add_prefix(dir);
+        }
+    }
+
+    /// Appends the overrides of `other` to `self`, so that they take precedence.
+    pub fn merge_into(mut self, other: Self) -> Self {
+        self.0.extend(other.0);
+        self
+    }
+}
+
+impl std::str::FromStr for FileOverrides {
+    type Err = &'static str;
+
     fn from_str(_: &str) -> Result<Self, Self::Err> {
-        Err("IgnoreList is not parsable")
+        Err("FileOverrides is not parsable")
     }
 }
 
+/// An option whose value was changed, as `(option_name, old_value, new_value)`.
+pub type ConfigOverride = (&'static str, String, String);
+
 /// Maps client-supplied options to Rustfmt's internals, mostly overriding
 /// values in a config with values from the command line.
 pub trait CliOptions {
     fn apply_to(&self, config: &mut Config);
     fn config_path(&self) -> Option<&Path>;
+
+    /// Applies the options to `config` and returns every option whose value was actually changed.
+    fn apply_to_reporting(&self, config: &mut Config) -> Vec<ConfigOverride> {
+        let original = config.clone();
+        self.apply_to(config);
+        original.changed_options(config)
+    }
 }
 
 /// The edition of the syntax and semantics of code (RFC 2052).
@@ -339,6 +791,27 @@ impl Default for Edition {
     }
 }
 
+impl Edition {
+    /// Returns `true` if `ident` is a strict or reserved keyword in this edition, i.e., it can
+    /// only be used as an identifier in the raw form, e.g., `r#async`.
+    pub fn is_keyword(self, ident: &str) -> bool {
+        const KEYWORDS: &[&str] = &[
+            "abstract", "as", "become", "box", "break", "const", "continue", "crate", "do", "else",
+            "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+            "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+            "self", "Self", "static", "struct", "super", "trait", "true", "type", "typeof",
+            "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+        ];
+        const KEYWORDS_2018: &[&str] = &["async", "await", "dyn", "try"];
+
+        KEYWORDS.contains(&ident)
+            || match self {
+                Edition::Edition2015 => false,
+                Edition::Edition2018 => KEYWORDS_2018.contains(&ident),
+            }
+    }
+}
+
 impl From<Edition> for rustc_span::edition::Edition {
     fn from(edition: Edition) -> Self {
         match edition {
@@ -361,9 +834,14 @@ pub enum MatchArmLeadingPipe {
 
 #[cfg(test)]
 mod test {
-    use std::path::PathBuf;
+    use std::collections::HashSet;
+    use std::path::{Path, PathBuf};
 
-    use crate::config::IgnoreList;
+    use crate::config::{
+        from_named_style, has_issue_reference, with_style, BraceStyle, ControlBraceStyle,
+        ControlConstruct, Density, Edition, FileName, FileOverride, FileOverrides, FinalNewline,
+        IgnoreList, ListTactic, NewlineStyle, ReportTactic, WidthHeuristics,
+    };
 
     #[test]
     fn test_ignore_list_merge_into() {
@@ -397,4 +875,299 @@ mod test {
                 .collect()
         );
     }
+
+    #[test]
+    fn test_ignore_list_skip_module() {
+        let ignore_list: IgnoreList = "crate::generated, src/api.rs".parse().unwrap();
+
+        assert!(ignore_list.skip_module(&["crate", "generated", "api"]));
+        assert!(ignore_list.skip_module(&["generated"]));
+        assert!(!ignore_list.skip_module(&["crate", "generator"]));
+        assert!(!ignore_list.skip_module(&["crate", "api"]));
+        assert!(!ignore_list.skip_module(&["src", "api.rs"]));
+    }
+
+    #[test]
+    fn test_ignore_list_from_str() {
+        let expected: HashSet<PathBuf> = vec![PathBuf::from("gen/"), PathBuf::from("vendor/")]
+            .into_iter()
+            .collect();
+
+        let comma_list: IgnoreList = "gen/, vendor/".parse().unwrap();
+        assert_eq!(comma_list.path_set, expected);
+
+        let semicolon_list: IgnoreList = " gen/;vendor/; ".parse().unwrap();
+        assert_eq!(semicolon_list.path_set, expected);
+
+        let empty_list: IgnoreList = "".parse().unwrap();
+        assert!(empty_list.path_set.is_empty());
+    }
+
+    #[test]
+    fn test_ignore_list_skip_reason() {
+        let ignore_list: IgnoreList = "gen, gen/inner/, vendor, *.generated.rs".parse().unwrap();
+        let skip_reason = |path: &str| ignore_list.skip_reason(Path::new(path));
+
+        assert_eq!(
+            skip_reason("gen/inner/foo.rs").as_deref(),
+            Some(Path::new("gen/inner"))
+        );
+        assert_eq!(skip_reason("gen/foo.rs").as_deref(), Some(Path::new("gen")));
+        assert_eq!(
+            skip_reason("src/gen/foo.rs").as_deref(),
+            Some(Path::new("gen"))
+        );
+        assert_eq!(
+            skip_reason("src/api.generated.rs").as_deref(),
+            Some(Path::new("*.generated.rs"))
+        );
+        assert_eq!(skip_reason("general/foo.rs"), None);
+        assert_eq!(skip_reason("src/lib.rs"), None);
+    }
+
+    #[test]
+    fn test_ignore_list_skip_range() {
+        let ignore_list: IgnoreList = "src/generated.rs:10-20, vendor".parse().unwrap();
+        let generated = FileName::Real(PathBuf::from("src/generated.rs"));
+
+        assert!(!ignore_list.skip_range(&generated, 9));
+        assert!(ignore_list.skip_range(&generated, 10));
+        assert!(ignore_list.skip_range(&generated, 15));
+        assert!(ignore_list.skip_range(&generated, 20));
+        assert!(!ignore_list.skip_range(&generated, 21));
+        assert!(!ignore_list.skip_range(&FileName::Real(PathBuf::from("src/lib.rs")), 15));
+        assert!(!ignore_list.skip_range(&FileName::Real(PathBuf::from("vendor/lib.rs")), 15));
+        assert!(!ignore_list.skip_range(&FileName::Stdin, 15));
+    }
+
+    #[test]
+    fn test_control_brace_style_for_construct() {
+        let constructs = [
+            ControlConstruct::If,
+            ControlConstruct::IfLet,
+            ControlConstruct::While,
+            ControlConstruct::WhileLet,
+            ControlConstruct::Loop,
+            ControlConstruct::For,
+            ControlConstruct::Match,
+        ];
+        for &style in ControlBraceStyle::all_variants() {
+            for &construct in &constructs {
+                assert_eq!(style.for_construct(construct), style);
+            }
+        }
+    }
+
+    #[test]
+    fn test_variant_count() {
+        assert_eq!(NewlineStyle::VARIANT_COUNT, 4);
+        assert_eq!(Density::VARIANT_COUNT, 3);
+    }
+
+    #[test]
+    fn test_newline_style_into_static_str() {
+        let values: Vec<&'static str> = NewlineStyle::all_variants()
+            .iter()
+            .map(<&str>::from)
+            .collect();
+        assert_eq!(values, vec!["Auto", "Windows", "Unix", "Native"]);
+        let value: &'static str = NewlineStyle::Unix.into();
+        assert_eq!(value, "Unix");
+    }
+
+    #[test]
+    fn test_file_overrides_display() {
+        let file_override = |files: &str, key: &str, value: toml::Value| FileOverride {
+            files: files.parse().unwrap(),
+            options: vec![(key.to_owned(), value)].into_iter().collect(),
+        };
+        let file_overrides = FileOverrides(vec![
+            file_override("gen, api.rs", "max_width", toml::Value::Integer(120)),
+            file_override(
+                "tests",
+                "newline_style",
+                toml::Value::String("Unix".to_owned()),
+            ),
+        ]);
+
+        assert_eq!(FileOverrides::default().to_string(), "[]");
+        assert_eq!(
+            file_overrides.to_string(),
+            "[[file_overrides]]\n\
+             files = [\"api.rs\", \"gen\"]\n\
+             max_width = 120\n\
+             [[file_overrides]]\n\
+             files = [\"tests\"]\n\
+             newline_style = \"Unix\""
+        );
+    }
+
+    #[test]
+    fn test_with_style() {
+        let text = "One\nTwo\n";
+        assert_eq!(with_style(text, NewlineStyle::Windows), "One\r\nTwo\r\n");
+        assert_eq!(with_style(text, NewlineStyle::Unix), text);
+        assert_eq!(
+            with_style(text, NewlineStyle::Windows).replace("\r\n", "\n"),
+            text
+        );
+        assert_eq!(
+            with_style(text, NewlineStyle::Native),
+            with_style(text, NewlineStyle::Auto)
+        );
+    }
+
+    #[test]
+    fn test_edition_is_keyword() {
+        assert!(Edition::Edition2018.is_keyword("async"));
+        assert!(!Edition::Edition2015.is_keyword("async"));
+        assert!(Edition::Edition2018.is_keyword("dyn"));
+        assert!(!Edition::Edition2015.is_keyword("dyn"));
+        assert!(Edition::Edition2015.is_keyword("fn"));
+        assert!(Edition::Edition2018.is_keyword("fn"));
+        assert!(!Edition::Edition2018.is_keyword("union"));
+        assert!(!Edition::Edition2018.is_keyword("foo"));
+    }
+
+    #[test]
+    fn test_has_issue_reference() {
+        assert!(has_issue_reference("TODO(#5)"));
+        assert!(has_issue_reference("TODO(ISSUE-123): handle the error"));
+        assert!(has_issue_reference("FIXME: see JIRA-9"));
+        assert!(has_issue_reference(
+            "TODO: https://github.com/rust-lang/rustfmt/issues/3581"
+        ));
+        assert!(!has_issue_reference("TODO"));
+        assert!(!has_issue_reference("TODO: handle the error"));
+        assert!(!has_issue_reference("FIXME: a follow-up is needed"));
+        assert!(!has_issue_reference("TODO: handle UTF-8"));
+        assert!(!has_issue_reference("FIXME: SHA-1"));
+        assert!(!has_issue_reference("TODO: support x86-64"));
+        assert!(!has_issue_reference("TODO: see Jira-9"));
+    }
+
+    #[test]
+    fn test_report_tactic_unnumbered() {
+        assert!(ReportTactic::Unnumbered.should_report("TODO: handle the error"));
+        assert!(!ReportTactic::Unnumbered.should_report("TODO(#5)"));
+        assert!(ReportTactic::Always.should_report("TODO(#5)"));
+        assert!(!ReportTactic::Never.should_report("TODO"));
+    }
+
+    #[test]
+    fn test_final_newline_apply() {
+        let apply = |final_newline: FinalNewline, text: &str| {
+            let mut text = text.to_owned();
+            final_newline.apply(&mut text);
+            text
+        };
+        let inputs = ["fn main() {}", "fn main() {}\n", "fn main() {}\n\n\n"];
+
+        for input in &inputs {
+            assert_eq!(apply(FinalNewline::Ensure, input), "fn main() {}\n");
+            assert_eq!(apply(FinalNewline::Remove, input), "fn main() {}");
+            assert_eq!(apply(FinalNewline::Preserve, input), *input);
+        }
+    }
+
+    #[test]
+    fn test_from_named_style() {
+        assert_eq!(
+            from_named_style("kr"),
+            Some((
+                BraceStyle::AlwaysNextLine,
+                ControlBraceStyle::AlwaysSameLine
+            ))
+        );
+        assert_eq!(
+            from_named_style("Allman"),
+            Some((
+                BraceStyle::AlwaysNextLine,
+                ControlBraceStyle::AlwaysNextLine
+            ))
+        );
+        assert_eq!(
+            from_named_style("stroustrup"),
+            Some((
+                BraceStyle::AlwaysNextLine,
+                ControlBraceStyle::ClosingNextLine
+            ))
+        );
+        assert_eq!(from_named_style("gnu"), None);
+    }
+
+    #[test]
+    fn test_width_heuristics_comment_width() {
+        assert_eq!(WidthHeuristics::scaled(100).comment_width, 80);
+        assert_eq!(WidthHeuristics::scaled(120).comment_width, 96);
+        assert_eq!(WidthHeuristics::scaled(200).comment_width, 160);
+        assert_eq!(WidthHeuristics::set(120).comment_width, 120);
+        assert_eq!(WidthHeuristics::null().comment_width, 80);
+    }
+
+    #[test]
+    fn test_width_heuristics_hash() {
+        let mut set = HashSet::new();
+        set.insert(WidthHeuristics::scaled(100));
+        set.insert(WidthHeuristics::scaled(100));
+        assert_eq!(set.len(), 1);
+        set.insert(WidthHeuristics::null());
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_width_heuristics_scaled_small_max_width() {
+        let width_heuristics = WidthHeuristics::scaled(10);
+        assert!(width_heuristics.fn_call_width >= 1);
+        assert!(width_heuristics.attr_fn_like_width >= 1);
+        assert!(width_heuristics.struct_lit_width >= 1);
+        assert!(width_heuristics.struct_variant_width >= 1);
+        assert!(width_heuristics.array_width >= 1);
+        assert!(width_heuristics.chain_width >= 1);
+        assert!(width_heuristics.comment_width >= 1);
+        assert_eq!(width_heuristics.single_line_if_else_max_width, 50);
+        assert_eq!(width_heuristics, WidthHeuristics::scaled(100));
+    }
+
+    #[test]
+    fn test_density_wants_trailing_comma() {
+        let tactics = [
+            ListTactic::Vertical,
+            ListTactic::Horizontal,
+            ListTactic::HorizontalVertical,
+            ListTactic::LimitedHorizontalVertical(10),
+            ListTactic::Mixed,
+        ];
+        let expected = [
+            (Density::Compressed, [true, false, false, false, false]),
+            (Density::Tall, [true, false, false, false, false]),
+            (Density::Vertical, [true, false, true, true, true]),
+        ];
+        assert_eq!(expected.len(), Density::all_variants().len());
+        for (density, wants) in &expected {
+            for (tactic, want) in tactics.iter().zip(wants.iter()) {
+                assert_eq!(
+                    density.wants_trailing_comma(*tactic),
+                    *want,
+                    "{:?} with {:?}",
+                    density,
+                    tactic
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_density_next_prev() {
+        let variants = [Density::Compressed, Density::Tall, Density::Vertical];
+        let mut density = Density::Compressed;
+        for &expected in variants.iter().cycle().take(4) {
+            assert_eq!(density, expected);
+            density = density.next();
+        }
+        for &expected in variants.iter().rev().cycle().skip(1).take(4) {
+            assert_eq!(density, expected);
+            density = density.prev();
+        }
+    }
 }
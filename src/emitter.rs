@@ -2,6 +2,7 @@ pub use self::checkstyle::*;
 pub use self::diff::*;
 pub use self::files::*;
 pub use self::json::*;
+pub use self::list_changed::*;
 pub use self::modified_lines::*;
 pub use self::stdout::*;
 
@@ -15,6 +16,7 @@ pub mod checkstyle;
 pub mod diff;
 pub mod files;
 pub mod json;
+pub mod list_changed;
 pub mod modified_lines;
 pub mod rustfmt_diff;
 pub mod stdout;
@@ -76,6 +78,82 @@ pub enum EmitMode {
     /// This option is designed to be run in CI where a non-zero exit signifies
     /// non-standard code formatting. Used for `--check`.
     Diff,
+    /// Prints the path of each file whose formatting would change, one per line, and quits
+    /// with exit code 1 if there is any. Cheaper than `Diff` as no diff is computed.
+    ListChanged,
+}
+
+impl EmitMode {
+    /// Returns the exit code of a run in this mode. Errors always make the run fail, while
+    /// changes only do for the modes that are used to check the formatting.
+    pub fn exit_code(self, had_changes: bool, had_errors: bool) -> i32 {
+        match self {
+            _ if had_errors => 1,
+            EmitMode::Diff | EmitMode::Checkstyle | EmitMode::ListChanged if had_changes => 1,
+            _ => 0,
+        }
+    }
+}
+
+/// Counts the outcome of formatting each file, for a one-line summary after a run.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct FormatSummary {
+    /// Files whose formatted text differs from the original.
+    pub reformatted: usize,
+    /// Files that were already formatted.
+    pub unchanged: usize,
+    /// Files that could not be formatted without errors.
+    pub errored: usize,
+}
+
+impl FormatSummary {
+    /// Counts every file in `format_report`.
+    pub fn from_report(format_report: &FormatReport) -> Self {
+        let mut summary = FormatSummary::default();
+        for (_, format_result) in format_report.format_result() {
+            summary.add_file(
+                format_result.original_text() != format_result.formatted_text(),
+                format_result.errors_excluding_macro().count() > 0,
+            );
+        }
+        summary
+    }
+
+    /// Records a single file. A file with errors is only counted as errored.
+    pub fn add_file(&mut self, changed: bool, had_errors: bool) {
+        if had_errors {
+            self.errored += 1;
+        } else if changed {
+            self.reformatted += 1;
+        } else {
+            self.unchanged += 1;
+        }
+    }
+
+    /// Renders the summary, e.g., `4 files reformatted, 10 unchanged`, phrased after what `mode`
+    /// does with the changes.
+    pub fn render(&self, mode: EmitMode) -> String {
+        let files = |n: usize| format!("{} file{}", n, if n == 1 { "" } else { "s" });
+        let changes = match mode {
+            EmitMode::Files => format!("{} reformatted", files(self.reformatted)),
+            EmitMode::Diff | EmitMode::Checkstyle => {
+                let verb = if self.reformatted == 1 {
+                    "needs"
+                } else {
+                    "need"
+                };
+                format!("{} {} formatting", files(self.reformatted), verb)
+            }
+            EmitMode::Stdout | EmitMode::Json | EmitMode::ModifiedLines | EmitMode::ListChanged => {
+                format!("{} would be reformatted", files(self.reformatted))
+            }
+        };
+        let mut summary = format!("{}, {} unchanged", changes, self.unchanged);
+        if self.errored > 0 {
+            summary.push_str(&format!(", {} failed to format", self.errored));
+        }
+        summary
+    }
 }
 
 /// Client-preference for coloured output.
@@ -97,6 +175,17 @@ impl Color {
             Color::Never => false,
         }
     }
+
+    /// Converts to the equivalent `termcolor::ColorChoice`. `ColorChoice::Auto` also respects
+    /// the `NO_COLOR` and `TERM` environment variables.
+    #[cfg(feature = "termcolor")]
+    pub fn to_color_choice(self) -> termcolor::ColorChoice {
+        match self {
+            Color::Always => termcolor::ColorChoice::Always,
+            Color::Never => termcolor::ColorChoice::Never,
+            Color::Auto => termcolor::ColorChoice::Auto,
+        }
+    }
 }
 
 /// How chatty should Rustfmt be?
@@ -125,6 +214,7 @@ impl std::str::FromStr for EmitMode {
             "stdout" => Ok(EmitMode::Stdout),
             "checkstyle" => Ok(EmitMode::Checkstyle),
             "json" => Ok(EmitMode::Json),
+            "list-changed" => Ok(EmitMode::ListChanged),
             _ => Err(format!("unknown emit mode `{}`", s)),
         }
     }
@@ -136,6 +226,8 @@ pub struct EmitterConfig {
     pub color: Color,
     pub verbosity: Verbosity,
     pub print_filename: bool,
+    /// Skip files whose formatting did not change. Only used by `EmitMode::Stdout`.
+    pub skip_unchanged: bool,
 }
 
 impl Default for EmitterConfig {
@@ -145,10 +237,12 @@ impl Default for EmitterConfig {
             color: Color::Auto,
             verbosity: Verbosity::Normal,
             print_filename: false,
+            skip_unchanged: false,
         }
     }
 }
 
+/// Emits every formatted file in the report, ordered by `FileName`.
 pub fn emit_format_report<T>(
     format_report: FormatReport,
     out: &mut T,
@@ -195,5 +289,109 @@ fn create_emitter(emitter_config: EmitterConfig) -> Box<dyn Emitter> {
         EmitMode::ModifiedLines => Box::new(ModifiedLinesEmitter::default()),
         EmitMode::Checkstyle => Box::new(CheckstyleEmitter::default()),
         EmitMode::Diff => Box::new(DiffEmitter::new(emitter_config)),
+        EmitMode::ListChanged => Box::new(ListChangedEmitter::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NewlineStyle;
+    use std::path::PathBuf;
+
+    #[test]
+    fn emit_mode_exit_code() {
+        let modes = [
+            (EmitMode::Files, 0),
+            (EmitMode::Stdout, 0),
+            (EmitMode::Checkstyle, 1),
+            (EmitMode::Json, 0),
+            (EmitMode::ModifiedLines, 0),
+            (EmitMode::Diff, 1),
+            (EmitMode::ListChanged, 1),
+        ];
+        for &(mode, code_on_changes) in &modes {
+            assert_eq!(mode.exit_code(false, false), 0, "{:?}", mode);
+            assert_eq!(mode.exit_code(true, false), code_on_changes, "{:?}", mode);
+            assert_eq!(mode.exit_code(false, true), 1, "{:?}", mode);
+            assert_eq!(mode.exit_code(true, true), 1, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn render_format_summary() {
+        let summary = FormatSummary {
+            reformatted: 4,
+            unchanged: 10,
+            errored: 0,
+        };
+        assert_eq!(
+            summary.render(EmitMode::Files),
+            "4 files reformatted, 10 unchanged"
+        );
+        assert_eq!(
+            summary.render(EmitMode::Diff),
+            "4 files need formatting, 10 unchanged"
+        );
+
+        let mut summary = FormatSummary::default();
+        summary.add_file(true, false);
+        summary.add_file(false, false);
+        summary.add_file(true, true);
+        assert_eq!(
+            summary.render(EmitMode::Diff),
+            "1 file needs formatting, 1 unchanged, 1 failed to format"
+        );
+        assert_eq!(
+            summary.render(EmitMode::Stdout),
+            "1 file would be reformatted, 1 unchanged, 1 failed to format"
+        );
+    }
+
+    #[test]
+    fn stdout_emits_files_in_file_name_order() {
+        let report = FormatReport::new();
+        for name in &["b.rs", "c.rs", "a.rs"] {
+            report.add_format_result(
+                FileName::Real(PathBuf::from(name)),
+                FormatResult::success(
+                    format!("// {}\n", name),
+                    vec![],
+                    String::new(),
+                    NewlineStyle::Unix,
+                ),
+            );
+        }
+        report.add_format_result(
+            FileName::Stdin,
+            FormatResult::success(
+                "// stdin\n".to_owned(),
+                vec![],
+                String::new(),
+                NewlineStyle::Unix,
+            ),
+        );
+
+        let mut out = Vec::new();
+        let config = EmitterConfig {
+            emit_mode: EmitMode::Stdout,
+            verbosity: Verbosity::Quiet,
+            ..EmitterConfig::default()
+        };
+        emit_format_report(report, &mut out, config).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "// a.rs\n// b.rs\n// c.rs\n// stdin\n"
+        );
+    }
+
+    #[cfg(feature = "termcolor")]
+    #[test]
+    fn color_to_color_choice() {
+        use termcolor::ColorChoice;
+
+        assert_eq!(Color::Always.to_color_choice(), ColorChoice::Always);
+        assert_eq!(Color::Never.to_color_choice(), ColorChoice::Never);
+        assert_eq!(Color::Auto.to_color_choice(), ColorChoice::Auto);
     }
 }
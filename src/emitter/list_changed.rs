@@ -0,0 +1,65 @@
+use super::*;
+
+#[derive(Debug, Default)]
+pub struct ListChangedEmitter;
+
+impl Emitter for ListChangedEmitter {
+    fn emit_formatted_file(
+        &mut self,
+        output: &mut dyn Write,
+        FormattedFile {
+            filename,
+            original_text,
+            formatted_text,
+        }: FormattedFile<'_>,
+    ) -> Result<EmitterResult, EmitterError> {
+        // Comparing the texts stops at the first difference, so no diff is computed.
+        let has_diff = original_text != formatted_text;
+        if has_diff {
+            writeln!(output, "{}", filename)?;
+        }
+        Ok(EmitterResult { has_diff })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn lists_only_changed_files() {
+        let mut writer = Vec::new();
+        let mut emitter = ListChangedEmitter::default();
+        let files = [
+            (
+                "src/bin.rs",
+                "fn main() {\nfoo();\n}\n",
+                "fn main() {\n    foo();\n}\n",
+            ),
+            ("src/empty.rs", "fn empty() {}\n", "fn empty() {}\n"),
+            ("src/lib.rs", "fn lib( ) {}\n", "fn lib() {}\n"),
+            ("src/util.rs", "fn util() {}\n", "fn util() {}\n"),
+        ];
+        let mut has_diff = false;
+        for &(name, original_text, formatted_text) in &files {
+            let result = emitter
+                .emit_formatted_file(
+                    &mut writer,
+                    FormattedFile {
+                        filename: &FileName::Real(PathBuf::from(name)),
+                        original_text,
+                        formatted_text,
+                    },
+                )
+                .unwrap();
+            has_diff |= result.has_diff;
+        }
+
+        assert!(has_diff);
+        assert_eq!(
+            String::from_utf8(writer).unwrap(),
+            "src/bin.rs\nsrc/lib.rs\n"
+        );
+    }
+}
@@ -6,12 +6,14 @@ use crate::emitter::{EmitterConfig, Verbosity};
 #[derive(Debug)]
 pub struct StdoutEmitter {
     verbosity: Verbosity,
+    skip_unchanged: bool,
 }
 
 impl StdoutEmitter {
     pub fn new(config: EmitterConfig) -> Self {
         Self {
             verbosity: config.verbosity,
+            skip_unchanged: config.skip_unchanged,
         }
     }
 }
@@ -22,10 +24,13 @@ impl Emitter for StdoutEmitter {
         output: &mut dyn Write,
         FormattedFile {
             filename,
+            original_text,
             formatted_text,
-            ..
         }: FormattedFile<'_>,
     ) -> Result<EmitterResult, EmitterError> {
+        if self.skip_unchanged && original_text == formatted_text {
+            return Ok(EmitterResult::default());
+        }
         if self.verbosity != Verbosity::Quiet {
             writeln!(output, "{}:\n", filename)?;
         }
@@ -33,3 +38,44 @@ impl Emitter for StdoutEmitter {
         Ok(EmitterResult::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileName;
+    use std::path::PathBuf;
+
+    #[test]
+    fn skips_unchanged_files_when_config_is_enabled() {
+        let mut writer = Vec::new();
+        let mut emitter = StdoutEmitter::new(EmitterConfig {
+            skip_unchanged: true,
+            ..EmitterConfig::default()
+        });
+        let _ = emitter
+            .emit_formatted_file(
+                &mut writer,
+                FormattedFile {
+                    filename: &FileName::Real(PathBuf::from("src/bin.rs")),
+                    original_text: "fn main() {\nprintln!(\"Hello, world!\");\n}\n",
+                    formatted_text: "fn main() {\n    println!(\"Hello, world!\");\n}\n",
+                },
+            )
+            .unwrap();
+        let _ = emitter
+            .emit_formatted_file(
+                &mut writer,
+                FormattedFile {
+                    filename: &FileName::Real(PathBuf::from("src/lib.rs")),
+                    original_text: "fn empty() {}\n",
+                    formatted_text: "fn empty() {}\n",
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(writer).unwrap(),
+            "src/bin.rs:\n\nfn main() {\n    println!(\"Hello, world!\");\n}\n",
+        )
+    }
+}
@@ -81,6 +81,33 @@ impl<'a> Display for FormatReportFormatter<'a> {
                 };
                 writeln!(f, "{}\n", DisplayList::from(snippet))?;
             }
+
+            for warning in errors.warnings() {
+                let label = warning.to_string();
+                let origin = format!("{}:{}", file, warning.line());
+                let source = errors
+                    .original_text()
+                    .lines()
+                    .nth(warning.line().saturating_sub(1))
+                    .unwrap_or("");
+                let snippet = Snippet {
+                    title: Some(Annotation {
+                        id: None,
+                        label: Some(&label),
+                        annotation_type: AnnotationType::Warning,
+                    }),
+                    slices: vec![Slice {
+                        source,
+                        line_start: warning.line(),
+                        origin: Some(&origin),
+                        fold: false,
+                        annotations: vec![],
+                    }],
+                    footer: vec![],
+                    opt,
+                };
+                writeln!(f, "{}\n", DisplayList::from(snippet))?;
+            }
         }
 
         if self.report.has_errors() {
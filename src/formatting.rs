@@ -6,6 +6,7 @@ use rustc_ast::{ast, attr::HasAttrs};
 use rustc_span::symbol;
 
 pub(crate) use syntux::session::ParseSess;
+pub(crate) use utils::format_code_block;
 
 use crate::config::{Config, FileName};
 use crate::formatting::{
@@ -51,7 +52,7 @@ pub(crate) mod source_map;
 mod spanned;
 mod stmt;
 mod string;
-mod syntux;
+pub(crate) mod syntux;
 mod types;
 mod vertical;
 pub(crate) mod visitor;
@@ -138,17 +139,29 @@ fn format_project(
         }
 
         should_emit_verbose(input_is_stdin, operation_setting.verbosity, || {
+            let list_matches = parse_session.list_matches(path);
+            if let (Some(ignore), Some(_)) = (list_matches.ignore, list_matches.force_format) {
+                println!("force-formatting {} despite ignore `{}`", path, ignore);
+            }
             println!("Formatting {}", path)
         });
-        format_file(
-            &parse_session,
-            config,
-            &krate,
-            path,
-            &module,
-            &format_report,
-            &files,
-            original_snippet.clone(),
+        let file_config = config.for_file(path);
+        time_if_verbose(
+            input_is_stdin,
+            operation_setting.verbosity,
+            || {
+                format_file(
+                    &parse_session,
+                    &file_config,
+                    &krate,
+                    path,
+                    &module,
+                    &format_report,
+                    &files,
+                    original_snippet.clone(),
+                )
+            },
+            |duration| println!("Formatted {} in {}ms", path, duration.as_millis()),
         )?;
     }
     timer = timer.done_formatting();
@@ -219,7 +232,15 @@ fn format_file(
             OperationError::IoError(std::io::Error::from(std::io::ErrorKind::InvalidInput))
         })?)?,
     };
-    apply_newline_style(config.newline_style(), &mut visitor.buffer, &original_text);
+    config.final_newline().apply(&mut visitor.buffer);
+    let newline_conversion =
+        apply_newline_style(config.newline_style(), &mut visitor.buffer, &original_text);
+    if newline_conversion.converted {
+        debug!(
+            "converted line endings of {} to {:?}",
+            path, newline_conversion.style_used
+        );
+    }
 
     if visitor.macro_rewrite_failure {
         report.add_macro_format_failure(path.clone());
@@ -469,6 +490,27 @@ where
     }
 }
 
+/// Runs `f`, and passes the time it took to `report_duration` if verbose output is enabled.
+fn time_if_verbose<T, F, R>(
+    forbid_verbose_output: bool,
+    verbosity: Verbosity,
+    f: F,
+    report_duration: R,
+) -> T
+where
+    F: FnOnce() -> T,
+    R: FnOnce(Duration),
+{
+    if verbosity == Verbosity::Verbose && !forbid_verbose_output {
+        let start = Instant::now();
+        let result = f();
+        report_duration(start.elapsed());
+        result
+    } else {
+        f()
+    }
+}
+
 /// Result of formatting a snippet of code along with ranges of lines that didn't get formatted,
 /// i.e., that got returned as they were originally.
 #[derive(Debug, Clone, Default)]
@@ -528,3 +570,27 @@ impl Input {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn time_if_verbose_reports_once_per_file() {
+        let reported = Cell::new(0);
+        let report_duration = |_| reported.set(reported.get() + 1);
+
+        for file in &["a.rs", "b.rs", "c.rs"] {
+            let result = time_if_verbose(false, Verbosity::Verbose, || *file, report_duration);
+            assert_eq!(result, *file);
+        }
+        assert_eq!(reported.get(), 3);
+
+        time_if_verbose(false, Verbosity::Normal, || (), report_duration);
+        time_if_verbose(false, Verbosity::Quiet, || (), report_duration);
+        time_if_verbose(true, Verbosity::Verbose, || (), report_duration);
+        assert_eq!(reported.get(), 3);
+    }
+}
@@ -303,6 +303,11 @@ fn identify_comment(
             if trimmed_line.is_empty() {
                 hbl = true;
                 break;
+            } else if !style.is_doc_comment() && comment_style(trimmed_line, false).is_doc_comment()
+            {
+                // `//` is a prefix of `///` and `//!`, but merging a doc comment into a group of
+                // ordinary comments would change its meaning.
+                break;
             } else if trimmed_line.starts_with(line_start)
                 || comment_style(trimmed_line, false) == style
             {
@@ -1072,6 +1077,19 @@ pub(crate) fn contains_comment(text: &str) -> bool {
     CharClasses::new(text.chars()).any(|(kind, _)| kind.is_comment())
 }
 
+/// Returns the first comment in `snippet` and whether it starts on the same line as the code
+/// preceding `snippet`, or `None` if `snippet` contains no comment.
+pub(crate) fn extract_trailing_comment(snippet: &str) -> Option<(&str, bool)> {
+    let mut newline_found = false;
+    for (kind, _, sub_slice) in CommentCodeSlices::new(snippet) {
+        match kind {
+            CodeCharKind::Comment => return Some((sub_slice.trim(), !newline_found)),
+            CodeCharKind::Normal => newline_found |= sub_slice.contains('\n'),
+        }
+    }
+    None
+}
+
 pub(crate) struct CharClasses<T>
 where
     T: Iterator,
@@ -1744,6 +1762,27 @@ mod test {
         assert_eq!(None, iter.next());
     }
 
+    #[test]
+    fn extract_trailing_comment_on_same_line() {
+        assert_eq!(
+            extract_trailing_comment(" // comment\n    "),
+            Some(("// comment", true))
+        );
+    }
+
+    #[test]
+    fn extract_trailing_comment_on_next_line() {
+        assert_eq!(
+            extract_trailing_comment("\n    /* comment */\n"),
+            Some(("/* comment */", false))
+        );
+    }
+
+    #[test]
+    fn extract_trailing_comment_without_comment() {
+        assert_eq!(extract_trailing_comment(" ;\n    "), None);
+    }
+
     #[test]
     fn comment_code_slices_two() {
         let input = "// comment\n    test();";
@@ -1906,6 +1945,14 @@ if x == 3 {
                                       Shape::legacy(100, Indent::new(0, 0)),
                                       &wrap_config).unwrap();
         assert_eq!("// test1\n/*\n a bare line!\n\n      another bare line!\n*/", comment);
+
+        // Check that a doc comment is not merged into the preceding ordinary comments.
+        let comment = rewrite_comment(r#"// test1
+                                         //! test2"#,
+                                      false,
+                                      Shape::legacy(100, Indent::new(0, 0)),
+                                      &wrap_config).unwrap();
+        assert_eq!("// test1\n//! test2", comment);
     }
 
     // This is probably intended to be a non-test fn, but it is not used.
@@ -23,7 +23,7 @@ use crate::formatting::{
     matches::rewrite_match,
     overflow::{self, IntoOverflowableItem, OverflowableItem},
     pairs::{rewrite_all_pairs, rewrite_pair, PairParts},
-    rewrite::{Rewrite, RewriteContext},
+    rewrite::{BlockKind, Rewrite, RewriteContext},
     shape::{Indent, Shape},
     source_map::{LineRangeUtils, SpanUtils},
     spanned::Spanned,
@@ -367,7 +367,7 @@ pub(crate) fn format_expr(
                 let budget = shape.width.saturating_sub(9);
                 Some(format!(
                     "{}{}",
-                    "try ",
+                    keyword_block_prefix(context, "try", block, shape),
                     rewrite_block(
                         block,
                         Some(&expr.attrs),
@@ -379,14 +379,14 @@ pub(crate) fn format_expr(
             }
         }
         ast::ExprKind::Async(capture_by, _node_id, ref block) => {
-            let mover = if capture_by == ast::CaptureBy::Value {
-                "move "
+            let keyword = if capture_by == ast::CaptureBy::Value {
+                "async move"
             } else {
-                ""
+                "async"
             };
             if let rw @ Some(_) = rewrite_single_line_block(
                 context,
-                format!("{}{}", "async ", mover).as_str(),
+                format!("{} ", keyword).as_str(),
                 block,
                 Some(&expr.attrs),
                 None,
@@ -397,9 +397,8 @@ pub(crate) fn format_expr(
                 // 6 = `async `
                 let budget = shape.width.saturating_sub(6);
                 Some(format!(
-                    "{}{}{}",
-                    "async ",
-                    mover,
+                    "{}{}",
+                    keyword_block_prefix(context, keyword, block, shape),
                     rewrite_block(
                         block,
                         Some(&expr.attrs),
@@ -524,6 +523,29 @@ fn block_prefix(context: &RewriteContext<'_>, block: &ast::Block, shape: Shape)
     })
 }
 
+/// Returns the keyword that introduces a block, e.g., `async move`, followed by the separator
+/// before the opening brace. Like `unsafe` blocks, a non-empty block starts on the next line with
+/// `BraceStyle::AlwaysNextLine`.
+fn keyword_block_prefix(
+    context: &RewriteContext<'_>,
+    keyword: &str,
+    block: &ast::Block,
+    shape: Shape,
+) -> String {
+    match context.config.brace_style() {
+        BraceStyle::AlwaysNextLine
+            if block_has_statements(block) || block_contains_comment(context, block) =>
+        {
+            format!(
+                "{}{}",
+                keyword,
+                shape.indent.to_string_with_newline(&context.config)
+            )
+        }
+        _ => format!("{} ", keyword),
+    }
+}
+
 fn rewrite_single_line_block(
     context: &RewriteContext<'_>,
     prefix: &str,
@@ -559,7 +581,7 @@ pub(crate) fn rewrite_block_with_visitor(
 
     let mut visitor = FmtVisitor::from_context(context);
     visitor.block_indent = shape.indent;
-    visitor.is_if_else_block = context.is_if_else_block();
+    visitor.block_kind = context.block_kind();
     match (block.rules, label) {
         (ast::BlockCheckMode::Unsafe(..), _) | (ast::BlockCheckMode::Default, Some(_)) => {
             let snippet = context.snippet(block.span);
@@ -1083,10 +1105,15 @@ impl<'a> Rewrite for ControlFlow<'a> {
             ..shape
         };
         let block_str = {
-            let old_val = context.is_if_else_block.replace(self.else_block.is_some());
+            let block_kind = if self.else_block.is_some() {
+                BlockKind::IfElse
+            } else {
+                BlockKind::Other
+            };
+            let old_val = context.block_kind.replace(block_kind);
             let result =
                 rewrite_block_with_visitor(context, "", self.block, None, None, block_shape, true);
-            context.is_if_else_block.replace(old_val);
+            context.block_kind.replace(old_val);
             result?
         };
 
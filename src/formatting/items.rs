@@ -2703,12 +2703,10 @@ fn rewrite_params(
     )
     .collect();
 
+    let density = context.config.fn_params_layout();
     let tactic = definitive_tactic(
         &param_items,
-        context
-            .config
-            .fn_params_layout()
-            .to_list_tactic(param_items.len()),
+        density.to_list_tactic(param_items.len()),
         Separator::Comma,
         one_line_budget,
     );
@@ -2731,6 +2729,7 @@ fn rewrite_params(
     let fmt = ListFormatting::new(Shape::legacy(budget, indent), context.config)
         .tactic(tactic)
         .trailing_separator(trailing_separator)
+        .density(density)
         .ends_with_newline(tactic.ends_with_newline(context.config.indent_style()))
         .preserve_newline(true);
     write_list(&param_items, &fmt)
@@ -6,7 +6,7 @@ use std::iter::Peekable;
 use rustc_span::BytePos;
 use unicode_segmentation::UnicodeSegmentation;
 
-use crate::config::{lists::*, Config, IndentStyle};
+use crate::config::{lists::*, Config, Density, IndentStyle};
 use crate::formatting::{
     comment::{find_comment_end, rewrite_comment, FindUncommented},
     rewrite::RewriteContext,
@@ -22,6 +22,8 @@ pub(crate) struct ListFormatting<'a> {
     tactic: DefinitiveListTactic,
     separator: &'a str,
     trailing_separator: SeparatorTactic,
+    // Decides the trailing separator when `trailing_separator` is `SeparatorTactic::Vertical`.
+    density: Density,
     separator_place: SeparatorPlace,
     shape: Shape,
     // Non-expressions, e.g., items, will have a new line at the end of the list.
@@ -42,6 +44,7 @@ impl<'a> ListFormatting<'a> {
             tactic: DefinitiveListTactic::Vertical,
             separator: ",",
             trailing_separator: SeparatorTactic::Never,
+            density: Density::Tall,
             separator_place: SeparatorPlace::Back,
             shape,
             ends_with_newline: true,
@@ -67,6 +70,11 @@ impl<'a> ListFormatting<'a> {
         self
     }
 
+    pub(crate) fn density(mut self, density: Density) -> Self {
+        self.density = density;
+        self
+    }
+
     pub(crate) fn separator_place(mut self, separator_place: SeparatorPlace) -> Self {
         self.separator_place = separator_place;
         self
@@ -96,7 +104,16 @@ impl<'a> ListFormatting<'a> {
         match self.trailing_separator {
             // We always put separator in front.
             SeparatorTactic::Always => true,
-            SeparatorTactic::Vertical => self.tactic == DefinitiveListTactic::Vertical,
+            SeparatorTactic::Vertical => {
+                let tactic = match self.tactic {
+                    DefinitiveListTactic::Vertical => ListTactic::Vertical,
+                    DefinitiveListTactic::Horizontal => ListTactic::Horizontal,
+                    DefinitiveListTactic::Mixed | DefinitiveListTactic::SpecialMacro(..) => {
+                        ListTactic::Mixed
+                    }
+                };
+                self.density.wants_trailing_comma(tactic)
+            }
             SeparatorTactic::Never => {
                 self.tactic == DefinitiveListTactic::Vertical && self.separator_place.is_front()
             }
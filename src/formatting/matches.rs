@@ -5,7 +5,9 @@ use std::iter::repeat;
 use rustc_ast::{ast, ptr};
 use rustc_span::{BytePos, Span};
 
-use crate::config::{lists::*, Config, ControlBraceStyle, IndentStyle, MatchArmLeadingPipe};
+use crate::config::{
+    lists::*, Config, ControlBraceStyle, ControlConstruct, IndentStyle, MatchArmLeadingPipe,
+};
 use crate::formatting::{
     comment::{combine_strs_with_missing_comments, rewrite_comment},
     expr::{
@@ -13,7 +15,7 @@ use crate::formatting::{
         rewrite_cond, ExprType, RhsTactics,
     },
     lists::{itemize_list, write_list, ListFormatting},
-    rewrite::{Rewrite, RewriteContext},
+    rewrite::{BlockKind, Rewrite, RewriteContext},
     shape::Shape,
     source_map::SpanUtils,
     spanned::Spanned,
@@ -87,7 +89,11 @@ pub(crate) fn rewrite_match(
     };
     let cond_str = cond.rewrite(context, cond_shape)?;
     let alt_block_sep = &shape.indent.to_string_with_newline(context.config);
-    let block_sep = match context.config.control_brace_style() {
+    let control_brace_style = context
+        .config
+        .control_brace_style()
+        .for_construct(ControlConstruct::Match);
+    let block_sep = match control_brace_style {
         ControlBraceStyle::AlwaysNextLine => alt_block_sep,
         _ if last_line_extendable(&cond_str) => " ",
         // 2 = ` {`
@@ -373,6 +379,19 @@ fn rewrite_match_body(
     let comma = arm_comma(context.config, body, is_last);
     let alt_block_sep = &shape.indent.to_string_with_newline(context.config);
 
+    // A comment before the closing brace of the arm block may be on top of the next arm.
+    let format_body = |body_shape: Shape| {
+        let block_kind = if is_block && !is_last {
+            BlockKind::MatchArm
+        } else {
+            BlockKind::Other
+        };
+        let old_val = context.block_kind.replace(block_kind);
+        let result = format_expr(body, ExprType::Statement, context, body_shape);
+        context.block_kind.replace(old_val);
+        result
+    };
+
     let combine_orig_body = |body_str: &str| {
         let block_sep = match context.config.control_brace_style() {
             ControlBraceStyle::AlwaysNextLine if is_block => alt_block_sep,
@@ -482,10 +501,7 @@ fn rewrite_match_body(
     let orig_body = if forbid_same_line || !arrow_comment.is_empty() {
         None
     } else if let Some(body_shape) = orig_body_shape {
-        let rewrite = nop_block_collapse(
-            format_expr(body, ExprType::Statement, context, body_shape),
-            body_shape.width,
-        );
+        let rewrite = nop_block_collapse(format_body(body_shape), body_shape.width);
 
         match rewrite {
             Some(ref body_str)
@@ -505,7 +521,7 @@ fn rewrite_match_body(
     // Try putting body on the next line and see if it looks better.
     let next_line_body_shape = Shape::indented(next_line_indent, context.config);
     let next_line_body = nop_block_collapse(
-        format_expr(body, ExprType::Statement, context, next_line_body_shape),
+        format_body(next_line_body_shape),
         next_line_body_shape.width,
     );
     match (orig_body, next_line_body) {
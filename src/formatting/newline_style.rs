@@ -10,19 +10,54 @@ pub(crate) fn apply_newline_style(
     newline_style: NewlineStyle,
     formatted_text: &mut String,
     raw_input_text: &str,
-) {
-    *formatted_text = match effective_newline_style(newline_style, raw_input_text) {
-        EffectiveNewlineStyle::Windows => convert_to_windows_newlines(formatted_text),
-        EffectiveNewlineStyle::Unix => convert_to_unix_newlines(formatted_text),
+) -> NewlineConversion {
+    let effective_newline_style = effective_newline_style(newline_style, raw_input_text);
+    let converted = match effective_newline_style {
+        EffectiveNewlineStyle::Windows => {
+            let transformed = convert_to_windows_newlines(formatted_text);
+            let converted = transformed != *formatted_text;
+            *formatted_text = transformed;
+            converted
+        }
+        // Avoid rewriting the whole text when it is already using unix newlines.
+        EffectiveNewlineStyle::Unix if formatted_text.contains(CARRIAGE_RETURN) => {
+            let transformed = convert_to_unix_newlines(formatted_text);
+            let converted = transformed != *formatted_text;
+            *formatted_text = transformed;
+            converted
+        }
+        EffectiveNewlineStyle::Unix => false,
+    };
+    NewlineConversion {
+        converted,
+        style_used: effective_newline_style.into(),
     }
 }
 
+/// The outcome of applying a newline style to the formatted text.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct NewlineConversion {
+    /// `true` if the line endings of the text were changed.
+    pub(crate) converted: bool,
+    /// The concrete style that was applied, i.e., either `Windows` or `Unix`.
+    pub(crate) style_used: NewlineStyle,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum EffectiveNewlineStyle {
     Windows,
     Unix,
 }
 
+impl From<EffectiveNewlineStyle> for NewlineStyle {
+    fn from(effective_newline_style: EffectiveNewlineStyle) -> Self {
+        match effective_newline_style {
+            EffectiveNewlineStyle::Windows => NewlineStyle::Windows,
+            EffectiveNewlineStyle::Unix => NewlineStyle::Unix,
+        }
+    }
+}
+
 fn effective_newline_style(
     newline_style: NewlineStyle,
     raw_input_text: &str,
@@ -40,18 +75,28 @@ const CARRIAGE_RETURN: char = '\r';
 const WINDOWS_NEWLINE: &str = "\r\n";
 const UNIX_NEWLINE: &str = "\n";
 
-fn auto_detect_newline_style(raw_input_text: &str) -> EffectiveNewlineStyle {
-    let first_line_feed_pos = raw_input_text.chars().position(|ch| ch == LINE_FEED);
-    match first_line_feed_pos {
-        Some(first_line_feed_pos) => {
-            let char_before_line_feed_pos = first_line_feed_pos.saturating_sub(1);
-            let char_before_line_feed = raw_input_text.chars().nth(char_before_line_feed_pos);
-            match char_before_line_feed {
-                Some(CARRIAGE_RETURN) => EffectiveNewlineStyle::Windows,
-                _ => EffectiveNewlineStyle::Unix,
+impl NewlineStyle {
+    /// Detects the newline style from the first newline in `raw`, without decoding it as UTF-8.
+    /// This allows deciding the style from the first chunk of a file. Returns `Native` if `raw`
+    /// contains no newlines.
+    pub fn detect_bytes(raw: &[u8]) -> NewlineStyle {
+        match raw.iter().position(|&b| b == LINE_FEED as u8) {
+            Some(first_line_feed_pos)
+                if raw[..first_line_feed_pos].last() == Some(&(CARRIAGE_RETURN as u8)) =>
+            {
+                NewlineStyle::Windows
             }
+            Some(_) => NewlineStyle::Unix,
+            None => NewlineStyle::Native,
         }
-        None => native_newline_style(),
+    }
+}
+
+fn auto_detect_newline_style(raw_input_text: &str) -> EffectiveNewlineStyle {
+    match NewlineStyle::detect_bytes(raw_input_text.as_bytes()) {
+        NewlineStyle::Windows => EffectiveNewlineStyle::Windows,
+        NewlineStyle::Unix => EffectiveNewlineStyle::Unix,
+        _ => native_newline_style(),
     }
 }
 
@@ -84,6 +129,7 @@ fn convert_to_unix_newlines(formatted_text: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::with_style;
 
     #[test]
     fn auto_detects_unix_newlines() {
@@ -109,6 +155,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn detects_newline_style_from_bytes() {
+        assert_eq!(
+            NewlineStyle::detect_bytes(b"One\r\nTwo\nThree"),
+            NewlineStyle::Windows
+        );
+        assert_eq!(
+            NewlineStyle::detect_bytes(b"One\nTwo\r\nThree"),
+            NewlineStyle::Unix
+        );
+        assert_eq!(
+            NewlineStyle::detect_bytes(b"One Two Three"),
+            NewlineStyle::Native
+        );
+    }
+
     #[test]
     fn falls_back_to_native_newlines_if_no_newlines_are_found() {
         let expected_newline_style = if cfg!(windows) {
@@ -135,11 +197,11 @@ mod tests {
     #[test]
     fn auto_detects_and_applies_windows_newlines() {
         let formatted_text = "One\nTwo\nThree";
-        let raw_input_text = "One\r\nTwo\r\nThree";
+        let raw_input_text = with_style(formatted_text, NewlineStyle::Windows);
 
         let mut out = String::from(formatted_text);
-        apply_newline_style(NewlineStyle::Auto, &mut out, raw_input_text);
-        assert_eq!("One\r\nTwo\r\nThree", &out, "auto should detect 'crlf'");
+        apply_newline_style(NewlineStyle::Auto, &mut out, &raw_input_text);
+        assert_eq!(raw_input_text, out, "auto should detect 'crlf'");
     }
 
     #[test]
@@ -149,18 +211,54 @@ mod tests {
 
         let mut out = String::from(formatted_text);
         apply_newline_style(NewlineStyle::Auto, &mut out, raw_input_text);
+        assert_eq!(
+            with_style(formatted_text, NewlineStyle::Native),
+            out,
+            "auto should fall back to native newlines"
+        );
+    }
 
-        if cfg!(windows) {
-            assert_eq!(
-                "One\r\nTwo\r\nThree", &out,
-                "auto-native-windows should detect 'crlf'"
-            );
-        } else {
-            assert_eq!(
-                "One\nTwo\nThree", &out,
-                "auto-native-unix should detect 'lf'"
-            );
-        }
+    #[test]
+    fn reports_conversion_only_when_text_changed() {
+        let apply = |newline_style: NewlineStyle, text: &str| {
+            let mut out = String::from(text);
+            apply_newline_style(newline_style, &mut out, text)
+        };
+        let conversion = |converted: bool, style_used: NewlineStyle| NewlineConversion {
+            converted,
+            style_used,
+        };
+
+        assert_eq!(
+            apply(NewlineStyle::Windows, "One\nTwo"),
+            conversion(true, NewlineStyle::Windows)
+        );
+        assert_eq!(
+            apply(NewlineStyle::Windows, "One\r\nTwo"),
+            conversion(false, NewlineStyle::Windows)
+        );
+        assert_eq!(
+            apply(NewlineStyle::Unix, "One\r\nTwo"),
+            conversion(true, NewlineStyle::Unix)
+        );
+        assert_eq!(
+            apply(NewlineStyle::Unix, "One\nTwo\r"),
+            conversion(false, NewlineStyle::Unix)
+        );
+        assert_eq!(
+            apply(NewlineStyle::Auto, "One\r\nTwo"),
+            conversion(false, NewlineStyle::Windows)
+        );
+    }
+
+    #[test]
+    fn applying_unix_newlines_does_not_reallocate_unix_text() {
+        let mut out = "One\nTwo\nThree\n".repeat(100_000);
+        let ptr = out.as_ptr();
+        let capacity = out.capacity();
+        apply_newline_style(NewlineStyle::Unix, &mut out, "One\nTwo\nThree\n");
+        assert_eq!(ptr, out.as_ptr());
+        assert_eq!(capacity, out.capacity());
     }
 
     #[test]
@@ -238,6 +336,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn converts_windows_newlines_to_unix_and_back_idempotently() {
+        let windows = "One\r\nTwo\r\nThree\r\n";
+        let unix = "One\nTwo\nThree\n";
+
+        let mut text = String::from(windows);
+        assert!(apply_newline_style(NewlineStyle::Unix, &mut text, windows).converted);
+        assert_eq!(text, unix);
+        assert!(!apply_newline_style(NewlineStyle::Unix, &mut text, windows).converted);
+        assert_eq!(text, unix);
+
+        assert!(apply_newline_style(NewlineStyle::Windows, &mut text, unix).converted);
+        assert_eq!(text, windows);
+        assert!(!apply_newline_style(NewlineStyle::Windows, &mut text, unix).converted);
+        assert_eq!(text, windows);
+    }
+
     fn test_newlines_are_applied_correctly(
         input: &str,
         expected: &str,
@@ -1,5 +1,6 @@
 use std::cell::{Ref, RefCell};
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
 use std::rc::Rc;
 
 use crate::formatting::FormattedSnippet;
@@ -24,9 +25,53 @@ pub struct FormatResult {
     original_snippet: String,
     formatted_snippet: FormattedSnippet,
     format_errors: HashSet<FormatError>,
+    warnings: Vec<FormatWarning>,
     newline_style: NewlineStyle,
 }
 
+/// A part of the input which rustfmt left as-is instead of formatting it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatWarning {
+    /// The remainder of a block starting at the given line lies outside of `file_lines`.
+    OutOfFileLinesRange(usize),
+    /// The comment at the given line could not be rewritten.
+    UnformattedComment(usize),
+}
+
+impl FormatWarning {
+    /// Returns the line of the input where the original text was kept.
+    pub fn line(self) -> usize {
+        match self {
+            FormatWarning::OutOfFileLinesRange(line) | FormatWarning::UnformattedComment(line) => {
+                line
+            }
+        }
+    }
+
+    /// Returns `true` if rustfmt failed to format this part of the input, rather than leaving it
+    /// as-is by design, as with the parts outside of `file_lines`.
+    pub fn is_fallback(self) -> bool {
+        match self {
+            FormatWarning::OutOfFileLinesRange(_) => false,
+            FormatWarning::UnformattedComment(_) => true,
+        }
+    }
+}
+
+impl fmt::Display for FormatWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatWarning::OutOfFileLinesRange(_) => write!(
+                f,
+                "the rest of the block is outside of `file_lines` and was left as-is"
+            ),
+            FormatWarning::UnformattedComment(_) => {
+                write!(f, "the comment could not be formatted and was left as-is")
+            }
+        }
+    }
+}
+
 /// The inclusive range of the input which was not formatted, represented by a pair of line numbers.
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct NonFormattedRange {
@@ -49,6 +94,7 @@ impl FormatResult {
             original_snippet,
             formatted_snippet,
             format_errors: HashSet::new(),
+            warnings: vec![],
             newline_style,
         }
     }
@@ -63,6 +109,11 @@ impl FormatResult {
             .filter(|e| e.kind() != ErrorKind::MacroFormatError)
     }
 
+    /// Returns the parts of the input which were left unformatted, in the order they were met.
+    pub fn warnings(&self) -> &[FormatWarning] {
+        &self.warnings
+    }
+
     /// Return the newline style used to format the result.
     pub fn newline_style(&self) -> NewlineStyle {
         self.newline_style
@@ -157,6 +208,15 @@ impl FormatReport {
         );
     }
 
+    pub(crate) fn add_warning(&self, file_name: FileName, warning: FormatWarning) {
+        self.format_result
+            .borrow_mut()
+            .entry(file_name)
+            .or_default()
+            .warnings
+            .push(warning);
+    }
+
     pub(crate) fn add_format_error(&self, file_name: FileName, format_error: FormatError) {
         self.format_result
             .borrow_mut()
@@ -197,6 +257,12 @@ impl FormatReport {
 
     pub fn has_failing_errors(&self, file_config_map: HashMap<FileName, &Config>) -> bool {
         self.has_any_matching_format_result(|(file_name, format_result)| {
+            let error_on_warnings = file_config_map
+                .get(file_name)
+                .map_or(false, |config| config.error_on_warnings());
+            if error_on_warnings && format_result.warnings.iter().any(|w| w.is_fallback()) {
+                return true;
+            }
             format_result.has_any_matching_errors(|e| match e.kind() {
                 ErrorKind::BadAttr | ErrorKind::DeprecatedAttr => true,
                 ErrorKind::LicenseCheck => {
@@ -228,6 +294,11 @@ impl FormatReport {
         })
     }
 
+    /// Returns `true` if the original text was kept for a part of any file.
+    pub fn has_format_warnings(&self) -> bool {
+        self.has_any_matching_format_result(|(_, format_result)| !format_result.warnings.is_empty())
+    }
+
     pub fn has_errors(&self) -> bool {
         RefCell::borrow(&self.format_result)
             .iter()
@@ -338,6 +409,35 @@ mod test {
             );
         }
 
+        #[test]
+        fn false_with_out_of_file_lines_range_warning_and_config_enabled() {
+            let file_name = FileName::Real(PathBuf::from("foo.rs"));
+            let report = FormatReport::new();
+            report.add_warning(file_name.clone(), FormatWarning::OutOfFileLinesRange(3));
+            let mut config = Config::default();
+            config.set().error_on_warnings(true);
+            assert!(report.has_format_warnings());
+            assert!(!report.has_failing_errors(vec![(file_name, &config)].into_iter().collect()));
+        }
+
+        #[test]
+        fn true_with_format_warning_only_if_config_enabled() {
+            let file_name = FileName::Real(PathBuf::from("foo.rs"));
+            let report = FormatReport::new();
+            report.add_warning(file_name.clone(), FormatWarning::UnformattedComment(3));
+            assert!(report.has_format_warnings());
+            assert!(!report.has_errors());
+            assert!(!report.has_failing_errors(
+                vec![(file_name.clone(), &Config::default())]
+                    .into_iter()
+                    .collect()
+            ));
+
+            let mut config = Config::default();
+            config.set().error_on_warnings(true);
+            assert!(report.has_failing_errors(vec![(file_name, &config)].into_iter().collect()));
+        }
+
         #[test]
         fn true_with_license_check_and_config_enabled() {
             let file_name = FileName::Real(PathBuf::from("foo.rs"));
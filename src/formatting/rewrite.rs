@@ -6,7 +6,7 @@ use std::rc::Rc;
 use rustc_ast::ptr;
 use rustc_span::Span;
 
-use crate::config::{Config, IndentStyle};
+use crate::config::{ClosingCommentUnindent, Config, IndentStyle};
 use crate::formatting::{
     modules::FileModMap,
     report::{FormatReport, NonFormattedRange},
@@ -27,6 +27,30 @@ impl<T: Rewrite> Rewrite for ptr::P<T> {
     }
 }
 
+/// The construct a block belongs to, as far as the comment before its closing brace is concerned.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum BlockKind {
+    /// The block of an `if` followed by `else`.
+    IfElse,
+    /// The block of a match arm followed by another arm.
+    MatchArm,
+    Other,
+}
+
+impl BlockKind {
+    /// Returns `true` if a comment before the closing brace of a block of this kind may stay at
+    /// the indentation of the brace, to describe the `else` or arm that follows.
+    pub(crate) fn unindents_closing_comment(self, config: &Config) -> bool {
+        match self {
+            BlockKind::IfElse => true,
+            BlockKind::MatchArm => {
+                config.closing_comment_unindent() == ClosingCommentUnindent::IfElseAndMatchArms
+            }
+            BlockKind::Other => false,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct RewriteContext<'a> {
     pub(crate) parse_sess: &'a ParseSess,
@@ -35,9 +59,9 @@ pub(crate) struct RewriteContext<'a> {
     pub(crate) inside_macro: Rc<Cell<bool>>,
     // Force block indent style even if we are using visual indent style.
     pub(crate) use_block: Cell<bool>,
-    // When `is_if_else_block` is true, unindent the comment on top
-    // of the `else` or `else if`.
-    pub(crate) is_if_else_block: Cell<bool>,
+    // The kind of the block being rewritten, which decides whether the comment on top
+    // of the `else`, `else if` or next match arm is unindented.
+    pub(crate) block_kind: Cell<BlockKind>,
     // When rewriting chain, veto going multi line except the last element
     pub(crate) force_one_line_chain: Cell<bool>,
     pub(crate) snippet_provider: &'a SnippetProvider,
@@ -95,7 +119,7 @@ impl<'a> RewriteContext<'a> {
         self.inside_macro.replace(false);
     }
 
-    pub(crate) fn is_if_else_block(&self) -> bool {
-        self.is_if_else_block.get()
+    pub(crate) fn block_kind(&self) -> BlockKind {
+        self.block_kind.get()
     }
 }
@@ -16,9 +16,9 @@ use crate::formatting::{
     source_map::LineRangeUtils, utils::starts_with_newline, visitor::SnippetProvider,
 };
 use crate::result::OperationError;
-use ignore_path::IgnorePathSet;
+use ignore_path::{IgnorePathSet, ListMatches};
 
-mod ignore_path;
+pub(crate) mod ignore_path;
 
 /// ParseSess holds structs necessary for constructing a parser.
 pub(crate) struct ParseSess {
@@ -119,7 +119,7 @@ fn default_handler(
 
 impl ParseSess {
     pub(crate) fn new(config: &Config) -> Result<ParseSess, OperationError> {
-        let ignore_path_set = match IgnorePathSet::from_ignore_list(&config.ignore()) {
+        let ignore_path_set = match IgnorePathSet::from_config(config) {
             Ok(ignore_path_set) => Rc::new(ignore_path_set),
             Err(e) => return Err(OperationError::InvalidGlobPattern(e)),
         };
@@ -169,6 +169,22 @@ impl ParseSess {
         self.ignore_path_set.as_ref().is_match(&path)
     }
 
+    /// Returns `true` if every line of `span` is inside a range ignored by a `path:start-end`
+    /// entry of the `ignore` option.
+    pub(crate) fn in_ignored_line_range(&self, span: Span) -> bool {
+        // Looking up the lines of every node is not free, so skip it when it cannot matter.
+        if !self.ignore_path_set.has_line_ranges() {
+            return false;
+        }
+        let line_range = self.lookup_line_range(span);
+        self.ignore_path_set
+            .skip_lines(&self.span_to_filename(span), line_range.lo, line_range.hi)
+    }
+
+    pub(crate) fn list_matches(&self, path: &FileName) -> ListMatches<'_> {
+        self.ignore_path_set.as_ref().list_matches(path)
+    }
+
     pub(crate) fn set_silent_emitter(&mut self) {
         self.parse_sess.span_diagnostic = Handler::with_emitter(true, None, silent_emitter());
     }
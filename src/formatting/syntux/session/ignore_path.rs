@@ -1,37 +1,167 @@
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
 
-use crate::config::{FileName, IgnoreList};
+use crate::config::{line_range_entry, Config, FileName, IgnoreList};
 
 pub(crate) struct IgnorePathSet {
     ignore_set: Gitignore,
+    /// Paths that are formatted even if they are matched by `ignore_set`.
+    force_format_set: Gitignore,
+    /// The `path:start-end` entries of the `ignore` option, see `IgnoreList::skip_range`.
+    line_ranges: Vec<LineRange>,
+}
+
+/// The lines from `start` to `end` (1-based, inclusive) of the file matched by `file_set`.
+struct LineRange {
+    file_set: Gitignore,
+    start: usize,
+    end: usize,
+}
+
+/// The patterns of the `ignore` and `force_format` options that match a file. A file matched by
+/// both is formatted.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct ListMatches<'a> {
+    pub(crate) ignore: Option<&'a str>,
+    pub(crate) force_format: Option<&'a str>,
+}
+
+/// Returns the directory the entries of `ignore_list` are relative to.
+fn ignore_root(ignore_list: &IgnoreList) -> &Path {
+    ignore_list
+        .rustfmt_toml_path()
+        .parent()
+        .unwrap_or(&Path::new(""))
+}
+
+fn build_gitignore(ignore_list: &IgnoreList) -> Result<Gitignore, ignore::Error> {
+    let mut ignore_builder = GitignoreBuilder::new(ignore_root(ignore_list));
+
+    // Line ranges only apply to part of a file, see `IgnoreList::skip_range`. The later of two
+    // patterns matching the same path wins, so add them in a fixed order.
+    let mut ignore_paths = ignore_list
+        .into_iter()
+        .filter(|p| line_range_entry(p).is_none())
+        .collect::<Vec<_>>();
+    ignore_paths.sort();
+    for ignore_path in ignore_paths {
+        let mut line = normalize_path(ignore_path).to_string_lossy().into_owned();
+        // `Path` drops a trailing slash, which makes a pattern only match directories.
+        if ignore_path.to_string_lossy().ends_with('/') && !line.ends_with('/') {
+            line.push('/');
+        }
+        ignore_builder.add_line(None, &line)?;
+    }
+
+    ignore_builder.build()
+}
+
+fn build_line_ranges(ignore_list: &IgnoreList) -> Result<Vec<LineRange>, ignore::Error> {
+    ignore_list
+        .into_iter()
+        .filter_map(|p| line_range_entry(p))
+        .map(|(path, start, end)| {
+            let mut file_builder = GitignoreBuilder::new(ignore_root(ignore_list));
+            // Anchor the pattern at the root, so that the entry only covers the file at `path`
+            // rather than every file with the same name.
+            let path = normalize_path(path);
+            let line = format!("/{}", path.to_string_lossy().trim_start_matches('/'));
+            file_builder.add_line(None, &line)?;
+            Ok(LineRange {
+                file_set: file_builder.build()?,
+                start,
+                end,
+            })
+        })
+        .collect()
+}
+
+/// Returns the pattern of `set` that ignores `path` or one of its parents.
+fn matched_pattern<'a>(set: &'a Gitignore, path: &Path) -> Option<&'a str> {
+    match set.matched_path_or_any_parents(path, false) {
+        Match::Ignore(glob) => Some(glob.original()),
+        Match::None | Match::Whitelist(_) => None,
+    }
+}
+
+/// Lexically collapses `.` and `..` components of `path`, without touching the file system so
+/// that symlinks are left as they are.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir) | Some(Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            _ => normalized.push(component),
+        }
+    }
+    normalized
 }
 
 impl IgnorePathSet {
     pub(crate) fn from_ignore_list(ignore_list: &IgnoreList) -> Result<Self, ignore::Error> {
-        let root = ignore_list
-            .rustfmt_toml_path()
-            .parent()
-            .unwrap_or(&Path::new(""));
-        let mut ignore_builder = GitignoreBuilder::new(root);
-
-        for ignore_path in ignore_list {
-            ignore_builder.add_line(None, &ignore_path.to_string_lossy())?;
-        }
+        Ok(Self {
+            ignore_set: build_gitignore(ignore_list)?,
+            force_format_set: Gitignore::empty(),
+            line_ranges: build_line_ranges(ignore_list)?,
+        })
+    }
 
+    /// Builds the set from both the `ignore` and the `force_format` options of `config`.
+    pub(crate) fn from_config(config: &Config) -> Result<Self, ignore::Error> {
+        let ignore_list = config.ignore();
         Ok(Self {
-            ignore_set: ignore_builder.build()?,
+            ignore_set: build_gitignore(&ignore_list)?,
+            force_format_set: build_gitignore(config.force_format().as_ref())?,
+            line_ranges: build_line_ranges(&ignore_list)?,
         })
     }
 
+    pub(crate) fn has_line_ranges(&self) -> bool {
+        !self.line_ranges.is_empty()
+    }
+
+    /// Returns `true` if every line from `lo` to `hi` (1-based, inclusive) of `file_name` is
+    /// inside a range ignored by a `path:start-end` entry.
+    pub(crate) fn skip_lines(&self, file_name: &FileName, lo: usize, hi: usize) -> bool {
+        let path = match file_name {
+            FileName::Real(p) => normalize_path(p),
+            FileName::Stdin => return false,
+        };
+        let line_ranges = self
+            .line_ranges
+            .iter()
+            .filter(|r| r.file_set.matched(&path, false).is_ignore())
+            .collect::<Vec<_>>();
+        !line_ranges.is_empty()
+            && (lo..=hi).all(|line| line_ranges.iter().any(|r| r.start <= line && line <= r.end))
+    }
+
     pub(crate) fn is_match(&self, file_name: &FileName) -> bool {
+        let matches = self.list_matches(file_name);
+        matches.ignore.is_some() && matches.force_format.is_none()
+    }
+
+    /// Returns the patterns of each list that match `file_name`, so that a file which is both
+    /// ignored and force-formatted can be reported.
+    pub(crate) fn list_matches(&self, file_name: &FileName) -> ListMatches<'_> {
         match file_name {
-            FileName::Stdin => false,
-            FileName::Real(p) => self
-                .ignore_set
-                .matched_path_or_any_parents(p, false)
-                .is_ignore(),
+            FileName::Stdin => ListMatches::default(),
+            FileName::Real(p) => {
+                let p = &normalize_path(p);
+                ListMatches {
+                    ignore: matched_pattern(&self.ignore_set, p),
+                    force_format: matched_pattern(&self.force_format_set, p),
+                }
+            }
         }
     }
 }
@@ -40,8 +170,8 @@ impl IgnorePathSet {
 mod test {
     use std::path::{Path, PathBuf};
 
-    use super::IgnorePathSet;
-    use crate::config::{Config, FileName};
+    use super::{normalize_path, IgnorePathSet, ListMatches};
+    use crate::config::{Config, FileName, IgnoreList};
 
     #[test]
     fn test_ignore_path_set() {
@@ -60,4 +190,133 @@ mod test {
             _ => {}
         };
     }
+
+    #[test]
+    fn test_ignore_path_set_from_gitignore() {
+        let ignore_list =
+            IgnoreList::from_gitignore(Path::new("tests/config/ignore-list.gitignore")).unwrap();
+        assert_eq!(ignore_list.into_iter().count(), 6);
+
+        let ignore_path_set = IgnorePathSet::from_ignore_list(&ignore_list).unwrap();
+        let is_match = |path: &str| ignore_path_set.is_match(&FileName::Real(PathBuf::from(path)));
+
+        assert!(is_match("tests/config/target/debug/build.rs"));
+        assert!(is_match("tests/config/src/api.generated.rs"));
+        assert!(is_match("tests/config/src/vendor/lib.rs"));
+        assert!(!is_match("tests/config/src/vendor/keep.rs"));
+        assert!(!is_match("tests/config/src/lib.rs"));
+        assert!(is_match("tests/config/src/#notes.rs"));
+        assert!(is_match("tests/config/space "));
+        assert!(!is_match("tests/config/space"));
+    }
+
+    #[test]
+    fn test_ignore_path_set_distinguishes_directories_from_files() {
+        let ignore_path_set =
+            IgnorePathSet::from_ignore_list(&"foo, bar.rs".parse().unwrap()).unwrap();
+        let is_match = |path: &str| ignore_path_set.is_match(&FileName::Real(PathBuf::from(path)));
+
+        // `foo` matches the directory, not a file whose stem is `foo`.
+        assert!(is_match("foo/baz.rs"));
+        assert!(is_match("src/foo/baz.rs"));
+        assert!(!is_match("foo.rs"));
+        assert!(!is_match("src/foo.rs"));
+
+        // `bar.rs` matches the file, not a directory named `bar`.
+        assert!(is_match("bar.rs"));
+        assert!(!is_match("bar/baz.rs"));
+    }
+
+    #[test]
+    fn test_list_matches_reports_both_lists() {
+        if !crate::is_nightly_channel!() {
+            // `force_format` is unstable
+            return;
+        }
+        let toml = r#"
+ignore = ["target/"]
+force_format = ["target/gen/api.rs"]
+"#;
+        let config = Config::from_toml(toml, Path::new("")).unwrap();
+        let ignore_path_set = IgnorePathSet::from_config(&config).unwrap();
+        let list_matches =
+            |path: &str| ignore_path_set.list_matches(&FileName::Real(PathBuf::from(path)));
+
+        assert_eq!(
+            list_matches("target/gen/api.rs"),
+            ListMatches {
+                ignore: Some("target/"),
+                force_format: Some("target/gen/api.rs"),
+            }
+        );
+        assert!(!ignore_path_set.is_match(&FileName::Real(PathBuf::from("target/gen/api.rs"))));
+
+        let matches = list_matches("target/debug/build.rs");
+        assert_eq!(matches.ignore, Some("target/"));
+        assert_eq!(matches.force_format, None);
+
+        assert_eq!(list_matches("src/lib.rs"), ListMatches::default());
+        assert_eq!(
+            ignore_path_set.list_matches(&FileName::Stdin),
+            ListMatches::default()
+        );
+    }
+
+    #[test]
+    fn test_skip_lines() {
+        let ignore_path_set =
+            IgnorePathSet::from_ignore_list(&"src/generated.rs:10-20, vendor".parse().unwrap())
+                .unwrap();
+        let generated = FileName::Real(PathBuf::from("src/generated.rs"));
+
+        assert!(ignore_path_set.skip_lines(&generated, 10, 20));
+        assert!(ignore_path_set.skip_lines(&generated, 12, 12));
+        assert!(!ignore_path_set.skip_lines(&generated, 9, 12));
+        assert!(!ignore_path_set.skip_lines(&generated, 18, 21));
+        assert!(!ignore_path_set.skip_lines(&FileName::Real(PathBuf::from("src/lib.rs")), 12, 12));
+
+        let ignore_path_set = IgnorePathSet::from_ignore_list(&"vendor".parse().unwrap()).unwrap();
+        assert!(!ignore_path_set.skip_lines(&generated, 12, 12));
+    }
+
+    #[test]
+    fn test_skip_lines_is_relative_to_config_dir() {
+        let config = Config::from_toml(
+            r#"ignore = ["generated.rs:10-20"]"#,
+            Path::new("project/rustfmt.toml"),
+        )
+        .unwrap();
+        let ignore_path_set = IgnorePathSet::from_config(&config).unwrap();
+        let skip_lines =
+            |path: &str| ignore_path_set.skip_lines(&FileName::Real(PathBuf::from(path)), 12, 12);
+
+        assert!(skip_lines("project/generated.rs"));
+        assert!(!skip_lines("project/src/generated.rs"));
+        assert!(!skip_lines("other/generated.rs"));
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(
+            normalize_path(Path::new("./gen/foo.rs")),
+            Path::new("gen/foo.rs")
+        );
+        assert_eq!(normalize_path(Path::new("a/../gen")), Path::new("gen"));
+        assert_eq!(normalize_path(Path::new("../a/./b/..")), Path::new("../a"));
+        assert_eq!(normalize_path(Path::new("/../gen")), Path::new("/gen"));
+    }
+
+    #[test]
+    fn test_ignore_path_set_normalizes_dot_components() {
+        let ignore_path_set = IgnorePathSet::from_ignore_list(&"gen".parse().unwrap()).unwrap();
+        let is_match = |path: &str| ignore_path_set.is_match(&FileName::Real(PathBuf::from(path)));
+
+        assert!(is_match("./gen/foo.rs"));
+        assert!(is_match("a/../gen/foo.rs"));
+        assert!(!is_match("a/gen/../foo.rs"));
+
+        let ignore_path_set =
+            IgnorePathSet::from_ignore_list(&"./src/../gen/".parse().unwrap()).unwrap();
+        assert!(ignore_path_set.is_match(&FileName::Real(PathBuf::from("gen/foo.rs"))));
+    }
 }
@@ -354,6 +354,43 @@ pub(crate) fn count_newlines(input: &str) -> usize {
     bytecount::count(input.as_bytes(), b'\n')
 }
 
+/// Returns the number of line breaks in `input`, where each of `\n`, `\r\n` and a lone `\r`
+/// counts as a single line break.
+pub(crate) fn count_lines(input: &str) -> usize {
+    let bytes = input.as_bytes();
+    bytes
+        .iter()
+        .enumerate()
+        .filter(|&(i, &b)| b == b'\n' || (b == b'\r' && bytes.get(i + 1) != Some(&b'\n')))
+        .count()
+}
+
+/// Replaces each run of `\r`s that ends with `\n` by a single `\n`, so that artifacts of mixed
+/// line endings such as `\r\r\n` count as one line break. Lone `\r`s are kept.
+pub(crate) fn collapse_carriage_returns(input: &str) -> Cow<'_, str> {
+    if !input.contains('\r') {
+        return Cow::Borrowed(input);
+    }
+    let mut result = String::with_capacity(input.len());
+    let mut pending_carriage_returns = 0;
+    for c in input.chars() {
+        match c {
+            '\r' => pending_carriage_returns += 1,
+            '\n' => {
+                pending_carriage_returns = 0;
+                result.push('\n');
+            }
+            _ => {
+                result.extend(std::iter::repeat('\r').take(pending_carriage_returns));
+                pending_carriage_returns = 0;
+                result.push(c);
+            }
+        }
+    }
+    result.extend(std::iter::repeat('\r').take(pending_carriage_returns));
+    Cow::Owned(result)
+}
+
 // For format_missing and last_pos, need to use the source callsite (if applicable).
 // Required as generated code spans aren't guaranteed to follow on from the last span.
 macro_rules! source {
@@ -377,9 +414,11 @@ macro_rules! out_of_file_lines_range {
     };
 }
 
+// Like `--file-lines`, a `path:start-end` entry of `ignore` keeps the nodes within its lines as
+// they are.
 macro_rules! skip_out_of_file_lines_range {
     ($self:ident, $span:expr) => {
-        if out_of_file_lines_range!($self, $span) {
+        if out_of_file_lines_range!($self, $span) || $self.parse_sess.in_ignored_line_range($span) {
             return None;
         }
     };
@@ -387,7 +426,7 @@ macro_rules! skip_out_of_file_lines_range {
 
 macro_rules! skip_out_of_file_lines_range_visitor {
     ($self:ident, $span:expr) => {
-        if out_of_file_lines_range!($self, $span) {
+        if out_of_file_lines_range!($self, $span) || $self.parse_sess.in_ignored_line_range($span) {
             $self.push_rewrite($span, None);
             return;
         }
@@ -826,6 +865,27 @@ mod test {
         assert_eq!(remove_trailing_white_spaces(&s), s);
     }
 
+    #[test]
+    fn test_count_lines() {
+        assert_eq!(count_lines(""), 0);
+        assert_eq!(count_lines("a"), 0);
+        assert_eq!(count_lines("a\nb\n"), 2);
+        assert_eq!(count_lines("a\r\nb\r\n"), 2);
+        assert_eq!(count_lines("a\rb\r"), 2);
+        assert_eq!(count_lines("a\nb\r\nc\rd"), 3);
+        assert_eq!(count_lines("\r\r\n\n"), 3);
+    }
+
+    #[test]
+    fn test_collapse_carriage_returns() {
+        assert_eq!(collapse_carriage_returns("a\nb"), "a\nb");
+        assert_eq!(collapse_carriage_returns("a\r\nb\r\r\n"), "a\nb\n");
+        assert_eq!(collapse_carriage_returns("a\rb\r"), "a\rb\r");
+        // The threshold for a blank line in `close_block`.
+        assert_eq!(count_lines(&collapse_carriage_returns("\r\r\n    ")), 1);
+        assert_eq!(count_lines(&collapse_carriage_returns("\r\n\r\n    ")), 2);
+    }
+
     #[test]
     fn test_trim_left_preserve_layout() {
         let s = "aaa\n\tbbb\n    ccc";
@@ -7,7 +7,9 @@ use rustc_span::{symbol, BytePos, Pos, Span, DUMMY_SP};
 use crate::config::{BraceStyle, Config};
 use crate::formatting::{
     attr::*,
-    comment::{contains_comment, rewrite_comment, CodeCharKind, CommentCodeSlices},
+    comment::{
+        extract_trailing_comment, rewrite_comment, CodeCharKind, CommentCodeSlices, FindUncommented,
+    },
     items::{
         format_impl, format_trait, format_trait_alias, is_mod_decl, is_use_item,
         rewrite_associated_impl_type, rewrite_extern_crate, rewrite_opaque_impl_type,
@@ -15,8 +17,8 @@ use crate::formatting::{
     },
     macros::{macro_style, rewrite_macro, rewrite_macro_def, MacroPosition},
     modules::{FileModMap, Module},
-    report::{FormatReport, NonFormattedRange},
-    rewrite::{Rewrite, RewriteContext},
+    report::{FormatReport, FormatWarning, NonFormattedRange},
+    rewrite::{BlockKind, Rewrite, RewriteContext},
     shape::{Indent, Shape},
     skip::{is_skip_attr, SkipContext},
     source_map::{LineRangeUtils, SpanUtils},
@@ -24,9 +26,10 @@ use crate::formatting::{
     stmt::Stmt,
     syntux::session::ParseSess,
     utils::{
-        self, contains_skip, count_newlines, depr_skip_annotation, format_unsafety,
-        inner_attributes, last_line_contains_single_line_comment, last_line_width, mk_sp,
-        ptr_vec_to_ref_vec, rewrite_ident, starts_with_newline, stmt_expr,
+        self, collapse_carriage_returns, contains_skip, count_lines, count_newlines,
+        depr_skip_annotation, format_unsafety, inner_attributes,
+        last_line_contains_single_line_comment, last_line_width, mk_sp, ptr_vec_to_ref_vec,
+        rewrite_ident, starts_with_newline, stmt_expr,
     },
 };
 use crate::result::{ErrorKind, FormatError};
@@ -79,7 +82,7 @@ pub(crate) struct FmtVisitor<'a> {
     // FIXME: use an RAII util or closure for indenting
     pub(crate) block_indent: Indent,
     pub(crate) config: &'a Config,
-    pub(crate) is_if_else_block: bool,
+    pub(crate) block_kind: BlockKind,
     pub(crate) snippet_provider: &'a SnippetProvider,
     pub(crate) line_number: usize,
     /// List of 1-based line ranges which were annotated with skip
@@ -275,6 +278,8 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
 
         self.walk_block_stmts(b);
 
+        // Only a tail `return`, `break` or `continue` may get a semicolon. Any other tail
+        // expression, e.g., a `match`, `if` or `loop`, is the value of the block.
         if !b.stmts.is_empty() {
             if let Some(expr) = stmt_expr(&b.stmts[b.stmts.len() - 1]) {
                 if utils::semicolon_for_expr(&self.get_context(), expr) {
@@ -285,11 +290,23 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
 
         let rest_span = self.next_span(b.span.hi());
         if out_of_file_lines_range!(self, rest_span) {
+            let line = self.parse_sess.line_of_byte_pos(rest_span.lo());
+            self.push_warning(rest_span, FormatWarning::OutOfFileLinesRange(line));
             self.push_str(self.snippet(rest_span));
             self.block_indent = self.block_indent.block_unindent(self.config);
         } else {
-            // Ignore the closing brace.
-            let missing_span = self.next_span(b.span.hi() - brace_compensation);
+            // Ignore the closing brace. Search for it rather than assuming it is the last byte of
+            // the span, so that a `}` in a trailing comment is never mistaken for it.
+            let closing_brace_pos = if has_braces {
+                self.snippet(rest_span)
+                    .find_last_uncommented("}")
+                    .map_or(b.span.hi() - brace_compensation, |i| {
+                        self.last_pos + BytePos::from_usize(i)
+                    })
+            } else {
+                b.span.hi()
+            };
+            let missing_span = self.next_span(closing_brace_pos);
             self.close_block(missing_span, self.unindent_comment_on_closing_brace(b));
         }
         self.last_pos = source!(self, b.span).hi();
@@ -301,6 +318,9 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
         let mut prev_kind = CodeCharKind::Normal;
         let mut newline_inserted = false;
 
+        // A blank line before a trailing comment is kept unless blank lines are disallowed.
+        let keep_blank_line = config.blank_lines_upper_bound() > 0;
+
         let skip_normal = |s: &str| {
             let trimmed = s.trim();
             !trimmed.is_empty() && trimmed.chars().all(|c| c == ';')
@@ -318,11 +338,27 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
 
         let comment_snippet = self.snippet(span);
 
-        let align_to_right = if unindent_comment && contains_comment(&comment_snippet) {
-            let first_lines = comment_snippet.splitn(2, '/').next().unwrap_or("");
-            last_line_width(first_lines) > last_line_width(&comment_snippet)
-        } else {
-            false
+        // A comment on its own line that is indented further than the closing brace belongs to
+        // the block, so it stays aligned with the items of the block. The first such comment
+        // decides, so a comment on the line of the last statement is skipped.
+        let mut own_line_snippet = comment_snippet;
+        if let Some((comment, true)) = extract_trailing_comment(own_line_snippet) {
+            let comment_start = own_line_snippet.find(comment).unwrap_or(0);
+            let rest = &own_line_snippet[comment_start..];
+            let comment_end = if comment.starts_with("//") {
+                rest.find('\n')
+            } else {
+                rest.find("*/").map(|i| i + 2)
+            };
+            own_line_snippet = comment_end.map_or("", |i| &rest[i..]);
+        }
+        let align_to_right = match extract_trailing_comment(own_line_snippet) {
+            Some((comment, _)) if unindent_comment => {
+                let comment_start = own_line_snippet.find(comment).unwrap_or(0);
+                last_line_width(&own_line_snippet[..comment_start])
+                    > last_line_width(&comment_snippet)
+            }
+            _ => false,
         };
 
         let mut iter = CommentCodeSlices::with_offset(
@@ -357,7 +393,13 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
                     }
                     match comment_str {
                         Some(ref s) => self.push_str(s),
-                        None => self.push_str(&sub_slice),
+                        None => {
+                            let line = self
+                                .parse_sess
+                                .line_of_byte_pos(span.lo() + BytePos::from_usize(offset));
+                            self.push_warning(span, FormatWarning::UnformattedComment(line));
+                            self.push_str(&sub_slice);
+                        }
                     }
                 }
                 CodeCharKind::Normal if skip_normal(&sub_slice) => {
@@ -372,7 +414,7 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
                         continue;
                     }
 
-                    match count_newlines(&sub_slice) {
+                    match count_lines(&collapse_carriage_returns(&sub_slice)) {
                         0 if !prev_is_comment
                             || !last_line_contains_single_line_comment(&self.buffer) =>
                         {
@@ -381,12 +423,14 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
                         }
                         0 => {}
                         1 if prev_is_comment
-                            && last_line_contains_single_line_comment(&self.buffer) =>
+                            && last_line_contains_single_line_comment(&self.buffer)
+                            && keep_blank_line =>
                         {
                             self.push_str("\n")
                         }
                         1 => {}
-                        _ => self.push_str("\n"),
+                        _ if keep_blank_line => self.push_str("\n"),
+                        _ => {}
                     }
                     newline_inserted = true;
                     if unindent_comment && align_to_right {
@@ -409,7 +453,7 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
     }
 
     fn unindent_comment_on_closing_brace(&self, b: &ast::Block) -> bool {
-        self.is_if_else_block && !b.stmts.is_empty()
+        self.block_kind.unindents_closing_comment(self.config) && !b.stmts.is_empty()
     }
 
     // Note that this only gets called for function definitions. Required methods
@@ -822,6 +866,13 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
         self.last_pos = source!(self, span).hi();
     }
 
+    /// Records that the original text was kept for a part of `span`. Warnings go to the shared
+    /// report, so that those met by visitors created from a `RewriteContext` are not lost.
+    fn push_warning(&self, span: Span, warning: FormatWarning) {
+        let file_name = self.parse_sess.span_to_filename(span);
+        self.report.add_warning(file_name, warning);
+    }
+
     pub(crate) fn push_rewrite(&mut self, span: Span, rewrite: Option<String>) {
         self.format_missing_with_indent(source!(self, span).lo());
         self.push_rewrite_inner(span, rewrite);
@@ -880,7 +931,7 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
             last_pos: BytePos(0),
             block_indent: Indent::empty(),
             config,
-            is_if_else_block: false,
+            block_kind: BlockKind::Other,
             snippet_provider,
             line_number: 0,
             skipped_range: Rc::new(RefCell::new(vec![])),
@@ -976,7 +1027,12 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
     }
 
     fn walk_block_stmts(&mut self, b: &ast::Block) {
-        self.walk_stmts(&Stmt::from_ast_nodes(b.stmts.iter()))
+        self.format_stmts(&b.stmts)
+    }
+
+    /// Formats `stmts` at the current indent, without the braces of an enclosing block.
+    pub(crate) fn format_stmts(&mut self, stmts: &[ast::Stmt]) {
+        self.walk_stmts(&Stmt::from_ast_nodes(stmts.iter()))
     }
 
     fn format_mod(
@@ -1084,7 +1140,7 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
             config: self.config,
             inside_macro: Rc::new(Cell::new(false)),
             use_block: Cell::new(false),
-            is_if_else_block: Cell::new(false),
+            block_kind: Cell::new(BlockKind::Other),
             force_one_line_chain: Cell::new(false),
             snippet_provider: self.snippet_provider,
             macro_rewrite_failure: Cell::new(false),
@@ -12,13 +12,14 @@ extern crate lazy_static;
 use std::path::PathBuf;
 
 pub use crate::config::{
-    load_config, CliOptions, Config, Edition, FileLines, FileName, NewlineStyle, Range,
+    load_config, load_config_reporting, CliOptions, Config, Edition, FileLines, FileName,
+    NewlineStyle, Range,
 };
 pub use crate::emitter::rustfmt_diff::{ModifiedChunk, ModifiedLines};
 pub use crate::format_report_formatter::{FormatReportFormatter, FormatReportFormatterBuilder};
-pub use crate::formatting::report::{FormatReport, FormatResult};
+pub use crate::formatting::report::{FormatReport, FormatResult, FormatWarning};
 
-use crate::formatting::format_input_inner;
+use crate::formatting::{format_code_block, format_input_inner};
 use crate::{emitter::Verbosity, result::OperationError};
 
 #[cfg(feature = "config")]
@@ -64,6 +65,13 @@ pub fn format_inputs<'a>(
     Ok(format_report)
 }
 
+/// Formats a sequence of statements which is not enclosed in braces, e.g., the input of a REPL.
+/// Returns `None` if the statements cannot be parsed. The result does not end with a newline.
+pub fn format_stmts(stmts: &str, config: &Config) -> Option<String> {
+    // The statements are wrapped in a function, whose block is then formatted by the visitor.
+    format_code_block(stmts, config).map(|formatted| formatted.as_ref().to_owned())
+}
+
 /// The input to rustfmt.
 #[derive(Debug)]
 pub enum Input {
@@ -14,9 +14,9 @@ use structopt::StructOpt;
 use thiserror::Error;
 
 use rustfmt_nightly::{
-    emitter::{emit_format_report, EmitMode, EmitterConfig, Verbosity},
-    format_inputs, load_config, CliOptions, Config, Edition, FileLines, FileName,
-    FormatReportFormatterBuilder, Input, OperationSetting,
+    emitter::{emit_format_report, EmitMode, EmitterConfig, FormatSummary, Verbosity},
+    format_inputs, load_config, load_config_reporting, CliOptions, Config, Edition, FileLines,
+    FileName, FormatReportFormatterBuilder, Input, OperationSetting,
 };
 
 fn main() {
@@ -75,8 +75,11 @@ struct Opt {
     #[structopt(short, long)]
     check: bool,
     /// Specify the format of rustfmt's output.
-    #[cfg_attr(nightly, structopt(long, name = "files|stdout|checkstyle|json"))]
-    #[cfg_attr(not(nightly), structopt(long, name = "files|stdout"))]
+    #[cfg_attr(
+        nightly,
+        structopt(long, name = "files|stdout|checkstyle|json|list-changed")
+    )]
+    #[cfg_attr(not(nightly), structopt(long, name = "files|stdout|list-changed"))]
     emit: Option<Emit>,
     /// A path to the configuration file.
     #[structopt(long = "config-path", parse(from_os_str))]
@@ -97,6 +100,9 @@ struct Opt {
     /// Prints the names of files with diff.
     #[structopt(short = "l", long = "files-with-diff")]
     files_with_diff: bool,
+    /// Only print the files whose formatting changed when emitting to stdout.
+    #[structopt(long = "skip-unchanged")]
+    skip_unchanged: bool,
     /// Set options from command line.
     ///
     /// Set configuration options via command line by specifying a list of key-value pairs
@@ -177,6 +183,7 @@ impl Opt {
             emit_mode,
             verbosity: self.verbosity(),
             print_filename: self.files_with_diff,
+            skip_unchanged: self.skip_unchanged,
             ..EmitterConfig::default()
         }
     }
@@ -251,6 +258,7 @@ pub enum Emit {
     Stdout,
     Checkstyle,
     Json,
+    ListChanged,
 }
 
 impl Emit {
@@ -260,6 +268,7 @@ impl Emit {
             Emit::Json => EmitMode::Json,
             Emit::Checkstyle => EmitMode::Checkstyle,
             Emit::Stdout => EmitMode::Stdout,
+            Emit::ListChanged => EmitMode::ListChanged,
         }
     }
 }
@@ -271,6 +280,7 @@ impl fmt::Display for Emit {
             Emit::Stdout => f.write_str("stdout"),
             Emit::Checkstyle => f.write_str("checkstyle"),
             Emit::Json => f.write_str("json"),
+            Emit::ListChanged => f.write_str("list-changed"),
         }
     }
 }
@@ -284,6 +294,7 @@ impl FromStr for Emit {
             "stdout" => Ok(Emit::Stdout),
             "checkstyle" => Ok(Emit::Checkstyle),
             "json" => Ok(Emit::Json),
+            "list-changed" => Ok(Emit::ListChanged),
             _ => Err(format!("unknown --emit mode: {}", s)),
         }
     }
@@ -440,8 +451,12 @@ fn format_string(input: String, opt: Opt) -> Result<i32> {
         verbosity: Verbosity::Quiet,
     };
     let report = rustfmt_nightly::format(Input::Text(input), &config, setting)?;
+    let emitter_config = opt.emitter_config(EmitMode::Stdout);
 
-    if report.has_errors() {
+    let has_failing_errors =
+        report.has_failing_errors(vec![(FileName::Stdin, &config)].into_iter().collect());
+    // Warnings are expected when formatting a range of lines, so they are only shown on request.
+    if report.has_errors() || has_failing_errors || (opt.verbose && report.has_format_warnings()) {
         eprintln!(
             "{}",
             FormatReportFormatterBuilder::new(&report)
@@ -449,15 +464,13 @@ fn format_string(input: String, opt: Opt) -> Result<i32> {
                 .build()
         );
 
-        if !opt.force
-            && report.has_failing_errors(vec![(FileName::Stdin, &config)].into_iter().collect())
-        {
-            return Ok(1);
+        if !opt.force && has_failing_errors {
+            return Ok(emitter_config.emit_mode.exit_code(false, true));
         }
     }
 
-    let has_diff = emit_format_report(report, out, opt.emitter_config(EmitMode::Stdout))?;
-    Ok(if opt.check && has_diff { 1 } else { 0 })
+    let has_diff = emit_format_report(report, out, emitter_config)?;
+    Ok(emitter_config.emit_mode.exit_code(has_diff, false))
 }
 
 enum FileConfig {
@@ -520,7 +533,11 @@ fn format(opt: Opt) -> Result<i32> {
         return Err(format_err!("Error: `{}` is a directory", dir.display()));
     }
 
-    let (default_config, config_paths) = load_config(None, Some(&opt))?;
+    let (default_config, config_paths, overrides, warnings) =
+        load_config_reporting(None, Some(&opt))?;
+    for warning in &warnings {
+        eprintln!("Warning: {}", warning);
+    }
 
     if opt.verbose {
         if let Some(paths) = config_paths.as_ref() {
@@ -533,6 +550,12 @@ fn format(opt: Opt) -> Result<i32> {
                     .join(","),
             );
         }
+        for (name, old_value, new_value) in &overrides {
+            println!(
+                "Overriding `{}` from the command line: {} -> {}",
+                name, old_value, new_value
+            );
+        }
     }
 
     let setting = OperationSetting {
@@ -555,6 +578,7 @@ fn format(opt: Opt) -> Result<i32> {
         setting,
     )?;
 
+    let emitter_config = opt.emitter_config(EmitMode::Files);
     let print_formatting_errors = || {
         eprintln!(
             "{}",
@@ -564,38 +588,44 @@ fn format(opt: Opt) -> Result<i32> {
         );
     };
 
-    match (format_report.has_errors(), opt.force) {
+    let file_config_map = inputs
+        .iter()
+        .map(|p| {
+            (
+                FileName::Real(p.file.to_path_buf()),
+                if let FileConfig::Local(ref config, _) = p.config {
+                    config
+                } else {
+                    &default_config
+                },
+            )
+        })
+        .collect();
+    let has_failing_errors = format_report.has_failing_errors(file_config_map);
+    // Warnings are expected when formatting a range of lines, so they are only shown on request.
+    let show_warnings =
+        setting.verbosity == Verbosity::Verbose && format_report.has_format_warnings();
+    match (
+        format_report.has_errors() || has_failing_errors || show_warnings,
+        opt.force,
+    ) {
         (false, _) => {}
         (true, true) => print_formatting_errors(),
         (true, false) => {
             print_formatting_errors();
-            let file_config_map = inputs
-                .iter()
-                .map(|p| {
-                    (
-                        FileName::Real(p.file.to_path_buf()),
-                        if let FileConfig::Local(ref config, _) = p.config {
-                            config
-                        } else {
-                            &default_config
-                        },
-                    )
-                })
-                .collect();
-
-            if format_report.has_failing_errors(file_config_map) {
-                return Ok(1);
+            if has_failing_errors {
+                return Ok(emitter_config.emit_mode.exit_code(false, true));
             }
         }
     }
 
-    let has_diff = emit_format_report(
-        format_report,
-        &mut stdout(),
-        opt.emitter_config(EmitMode::Files),
-    )?;
-
-    Ok(if opt.check && has_diff { 1 } else { 0 })
+    let summary = FormatSummary::from_report(&format_report);
+    let has_diff = emit_format_report(format_report, &mut stdout(), emitter_config)?;
+    // Printed to stderr so that the output of the emitter stays machine readable.
+    if setting.verbosity != Verbosity::Quiet {
+        eprintln!("{}", summary.render(emitter_config.emit_mode));
+    }
+    Ok(emitter_config.emit_mode.exit_code(has_diff, false))
 }
 
 #[cfg(test)]
@@ -761,6 +791,60 @@ mod test {
         assert!(output.status.success());
     }
 
+    #[cfg(nightly)]
+    #[test]
+    fn verify_format_warnings_with_stdin() {
+        init_log();
+
+        let run = |args: &[&str]| {
+            let mut child = Command::new(rustfmt())
+                .arg("--file-lines")
+                .arg(r#"[{"file":"stdin","range":[1,1]}]"#)
+                .args(args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .expect("run with file-lines option failed");
+
+            {
+                let stdin = child.stdin.as_mut().expect("Failed to open stdin");
+                stdin
+                    .write_all(b"fn main() {\n    let x = 1;\n    let y = 2;\n}\n")
+                    .expect("Failed to write to rustfmt");
+            }
+            child
+                .wait_with_output()
+                .expect("Failed to wait on rustfmt child")
+        };
+        let exp_err = vec![
+            "\u{1b}[1;38;5;11mwarning\u{1b}[0m: \u{1b}[1mthe rest of the block is outside of ",
+            "`file_lines` and was left as-is",
+            "\u{1b}[0m\n \u{1b}[1;38;5;12m-->\u{1b}[0m <stdin>:3\n",
+            "\u{1b}[1;38;5;12m  |\u{1b}[0m\n",
+            "\u{1b}[1;38;5;12m3 |\u{1b}[0m     let y = 2;\n",
+            "\u{1b}[1;38;5;12m  |\u{1b}[0m\n\n\n",
+        ]
+        .join("");
+
+        // Formatting a range of lines leaves the rest of the block as-is by design.
+        let output = run(&[]);
+        assert!(output.status.success());
+        assert!(!output.stdout.is_empty());
+        assert!(output.stderr.is_empty());
+
+        let output = run(&["--verbose"]);
+        assert!(output.status.success());
+        assert!(!output.stdout.is_empty());
+        assert_eq!(String::from_utf8(output.stderr).unwrap(), exp_err);
+
+        // Only a part which rustfmt failed to format makes the run fail.
+        let output = run(&["--config", "error_on_warnings=true"]);
+        assert!(output.status.success());
+        assert!(!output.stdout.is_empty());
+        assert!(output.stderr.is_empty());
+    }
+
     #[cfg(test)]
     mod force {
         use super::*;
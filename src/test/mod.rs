@@ -10,9 +10,9 @@ use crate::emitter::rustfmt_diff::{make_diff, print_diff, Mismatch, ModifiedChun
 
 use crate::config::{Config, FileName, NewlineStyle};
 use crate::{
-    emitter::{emit_format_report, Color, EmitMode, EmitterConfig},
-    format, is_nightly_channel, FormatReport, FormatReportFormatterBuilder, Input, OperationError,
-    OperationSetting,
+    emitter::{emit_format_report, Color, EmitMode, EmitterConfig, Verbosity},
+    format, is_nightly_channel, FormatReport, FormatReportFormatterBuilder, FormatWarning, Input,
+    OperationError, OperationSetting,
 };
 
 mod configuration_snippet;
@@ -432,6 +432,38 @@ fn stdin_formatting_smoke_test() {
     assert_eq!(buf, b"<stdin>:\n\nfn main() {}\r\n");
 }
 
+#[test]
+fn out_of_file_lines_range_block_records_warning() {
+    init_log();
+    let input = Input::Text("fn main() {\n    let x = 1;\n    let y = 2;\n}\n".to_owned());
+    let mut config = Config::default();
+    config
+        .set()
+        .file_lines(r#"[{"file":"stdin","range":[1,1]}]"#.parse().unwrap());
+    let report = format(input, &config, OperationSetting::default()).unwrap();
+    let (file_name, format_result) = report.format_result().next().unwrap();
+    assert_eq!(file_name, &FileName::Stdin);
+    assert_eq!(
+        format_result.warnings(),
+        &[FormatWarning::OutOfFileLinesRange(3)]
+    );
+    assert_eq!(
+        FormatReportFormatterBuilder::new(&report).build().to_string(),
+        "warning: the rest of the block is outside of `file_lines` and was left as-is\n \
+         --> <stdin>:3\n  |\n3 |     let y = 2;\n  |\n\n"
+    );
+}
+
+#[test]
+fn format_stmts_without_braces() {
+    init_log();
+    let stmts = "let x=1;\nlet y = x+1 ;";
+    assert_eq!(
+        crate::format_stmts(stmts, &Config::default()).as_deref(),
+        Some("let x = 1;\nlet y = x + 1;")
+    );
+}
+
 #[test]
 fn stdin_parser_panic_caught() {
     init_log();
@@ -522,6 +554,76 @@ fn format_lines_errors_are_reported_with_tabs() {
     assert!(report.has_errors());
 }
 
+#[test]
+fn file_overrides_are_applied() {
+    init_log();
+    if !is_nightly_channel!() {
+        // `file_overrides` is unstable
+        return;
+    }
+    let path = Path::new("tests/file-overrides/wide.rs");
+    let original = fs::read_to_string(path).unwrap();
+    let formatted_text = |toml: &str| {
+        let config = Config::from_toml(toml, Path::new("")).unwrap();
+        let report = format_file(path, OperationSetting::default(), config).unwrap();
+        let (_, result) = report.format_result().next().unwrap();
+        result.formatted_text().to_owned()
+    };
+
+    let toml = r#"
+[[file_overrides]]
+files = ["wide.rs"]
+max_width = 120
+"#;
+    assert_eq!(formatted_text(toml), original);
+    assert_ne!(formatted_text(""), original);
+}
+
+#[test]
+fn emit_into_arbitrary_writer() {
+    init_log();
+    let config = Config::default();
+    let input = Input::Text("fn main( ) { }".to_owned());
+    let report = format(input, &config, OperationSetting::default()).unwrap();
+
+    let mut buffer: Vec<u8> = vec![];
+    emit_format_report(
+        report,
+        &mut buffer,
+        EmitterConfig {
+            emit_mode: EmitMode::Stdout,
+            verbosity: Verbosity::Quiet,
+            ..EmitterConfig::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(buffer, b"fn main() {}\n");
+}
+
+#[test]
+fn diff_ignores_line_endings_matching_newline_style() {
+    init_log();
+    let mut config = Config::default();
+    config.set().newline_style(NewlineStyle::Windows);
+    let input = Input::Text("fn main() {\r\n    println!(\"Hello\");\r\n}\r\n".to_owned());
+    let report = format(input, &config, OperationSetting::default()).unwrap();
+
+    let mut out = vec![];
+    let has_diff = emit_format_report(
+        report,
+        &mut out,
+        EmitterConfig {
+            emit_mode: EmitMode::Diff,
+            ..EmitterConfig::default()
+        },
+    )
+    .unwrap();
+
+    assert!(!has_diff);
+    assert!(out.is_empty());
+}
+
 // For each file, run rustfmt and collect the output.
 // Returns the number of files checked and the number of failures.
 fn check_files(files: Vec<PathBuf>, opt_config: &Option<PathBuf>) -> (Vec<FormatReport>, u32, u32) {
@@ -0,0 +1,3 @@
+fn main() {
+    let result = some_function_with_long_name(first_argument, second_argument, third_argument, fourth);
+}
@@ -0,0 +1,16 @@
+fn main() {
+//! directive
+ stmt();
+}
+
+mod generated {
+//! directive
+ fn stmt() {}
+}
+
+fn block_expr() {
+    let x = {
+//! directive
+ stmt()
+    };
+}
@@ -0,0 +1,12 @@
+// rustfmt-brace_style: AlwaysNextLine
+// Labeled block brace style
+#![feature(label_break_value)]
+
+fn lorem() -> usize
+{
+    let ipsum = 'dolor:   {
+        if sit() { break 'dolor 1; }
+        2
+    };
+    ipsum
+}
@@ -0,0 +1,21 @@
+// rustfmt-closing_comment_unindent: IfElse
+
+fn main() {
+    if x {
+        foo();
+    // `else` comment
+    } else {
+        bar();
+    }
+
+    match x {
+        Some(y) => {
+            foo(y);
+        // `None` comment
+        }
+        None => {
+            bar();
+        // Last arm
+        }
+    }
+}
@@ -0,0 +1,17 @@
+// rustfmt-closing_comment_unindent: IfElse
+
+fn main() {
+    if x {
+        foo();   // `foo` comment
+            // Block comment
+    } else {
+        bar();
+    }
+
+    if x {
+        foo(); // `foo` comment
+    // `else` comment
+    } else {
+        bar();
+    }
+}
@@ -0,0 +1,12 @@
+fn main() {
+  let x =   1;
+      // }
+}
+
+fn foo() {
+    bar();    // }
+}
+
+fn baz() {
+        /* } */
+}
@@ -0,0 +1,11 @@
+// A closure block with a single expression stays on the brace line when it fits.
+
+fn main() {
+    let add_one = |x: i32| -> i32 { x + 1 };
+    let overlong = |x: i32| -> i32 {
+        x + aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa + bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb
+    };
+    let statement = |x: i32| {
+        println!("{}", x);
+    };
+}
@@ -0,0 +1,6 @@
+// rustfmt-blank_lines_upper_bound: 0
+// Blank lines before a comment at the end of a block
+fn main() {
+    let x = 1;
+    // trailing comment
+}
@@ -0,0 +1,22 @@
+// rustfmt-brace_style: AlwaysNextLine
+// AlwaysNextLine brace style for blocks introduced by a keyword
+
+fn main()
+{
+    let f = async
+    {
+        let x = 1;
+        x
+    };
+    let g = async move
+    {
+        let y = 2;
+        y
+    };
+    let e = async {};
+    unsafe
+    {
+        let z = 3;
+    }
+    unsafe {}
+}
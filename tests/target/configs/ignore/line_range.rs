@@ -0,0 +1,9 @@
+// rustfmt-ignore: tests/**/configs/ignore/line_range.rs:5-8
+// Items within lines 5 to 8 are kept as they are.
+
+fn formatted() {}
+fn   generated ( a :u32)->u32{
+    a+1
+}
+const   X :u32=1;
+fn also_formatted() {}
@@ -0,0 +1,10 @@
+// rustfmt-reorder_imports: false
+// Do not reorder imports inside a block, but still format them
+
+fn main() {
+    use lorem;
+    use ipsum;
+    use dolor::sit;
+
+    lorem();
+}
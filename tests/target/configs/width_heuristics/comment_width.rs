@@ -0,0 +1,8 @@
+// rustfmt-width_heuristics: Scaled
+// rustfmt-max_width: 120
+// rustfmt-wrap_comments: true
+
+fn main() {
+    // Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor magna.
+    // exercitationem ullamco laboris.
+}
@@ -0,0 +1,9 @@
+// Blocks that only contain empty statements are empty
+
+fn main() {
+    if foo() {}
+    let x = {};
+    match x {
+        _ => {}
+    }
+}
@@ -0,0 +1,45 @@
+// A tail `match`, `if` or `loop` is the value of the block and never gets a semicolon, while a
+// statement keeps its own.
+
+fn tail_match(x: Option<u32>) -> u32 {
+    let y = x.unwrap_or(0);
+    match x {
+        Some(n) => n + y,
+        None => 0,
+    }
+}
+
+fn statement_match(x: Option<u32>) {
+    match x {
+        Some(n) => println!("{}", n),
+        None => {}
+    };
+}
+
+fn tail_if(b: bool) -> u32 {
+    let y = 1;
+    if b {
+        y
+    } else {
+        0
+    }
+}
+
+fn tail_loop() -> u32 {
+    let mut n = 0;
+    loop {
+        n += 1;
+        if n > 10 {
+            break n;
+        }
+    }
+}
+
+fn tail_block(x: Option<u32>) -> u32 {
+    {
+        match x {
+            Some(n) => n,
+            None => 0,
+        }
+    }
+}